@@ -12,6 +12,11 @@ use std::path::Path;
 /// Parses command-line arguments and dispatches to the appropriate mode:
 /// - `--hook`: Run as a Claude Code PreToolUse hook (reads JSON from stdin)
 /// - `--install`: Interactive installer to set up project configuration
+/// - `--validate`: Diagnose installation and configuration problems
+/// - `explain`: Show what the hook would do with a Bash command (dry run)
+/// - `test-rules`: Run the `[[tests]]` entries of the configuration
+/// - `suggest-mappings`: Suggest command mappings mined from command history
+/// - `lint`: Report cyclic, shadowed and unusable command rules
 /// - Default: Show usage information
 pub fn run_cli() -> Result<()> {
     let matches = Command::new("claude-hook-advisor")
@@ -23,7 +28,8 @@ pub fn run_cli() -> Result<()> {
                 .long("config")
                 .value_name("FILE")
                 .help("Path to configuration file")
-                .default_value(".claude-hook-advisor.toml"),
+                .default_value(".claude-hook-advisor.toml")
+                .global(true),
         )
         .arg(
             Arg::new("hook")
@@ -34,8 +40,9 @@ pub fn run_cli() -> Result<()> {
         .arg(
             Arg::new("replace")
                 .long("replace")
-                .help("Replace commands instead of blocking (experimental, not yet supported by Claude Code)")
-                .action(clap::ArgAction::SetTrue),
+                .help("Rewrite mapped commands in place instead of blocking them")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
         )
         .arg(
             Arg::new("install")
@@ -49,6 +56,18 @@ pub fn run_cli() -> Result<()> {
                 .help("Remove Claude Hook Advisor hooks from Claude Code settings")
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("validate")
+                .long("validate")
+                .help("Check installation and configuration health")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .help("Print --validate results as JSON")
+                .action(clap::ArgAction::SetTrue),
+        )
         .arg(
             Arg::new("history")
                 .long("history")
@@ -74,39 +93,124 @@ pub fn run_cli() -> Result<()> {
                 .help("Show only failed commands (non-zero exit codes)")
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("adoption")
+                .long("adoption")
+                .help("Show how often Claude adopted each rule's suggested replacement")
+                .action(clap::ArgAction::SetTrue),
+        )
         .arg(
             Arg::new("pattern")
                 .long("pattern")
                 .value_name("PATTERN")
                 .help("Filter commands by pattern (e.g., 'git', 'npm')"),
         )
+        .subcommand(
+            Command::new("explain")
+                .about("Show what the hook would do with a Bash command, without running it")
+                .arg(
+                    Arg::new("command")
+                        .long("command")
+                        .value_name("COMMAND")
+                        .help("Bash command to evaluate")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("cwd")
+                        .long("cwd")
+                        .value_name("DIR")
+                        .help("Working directory to evaluate in (default: current directory)"),
+                ),
+        )
+        .subcommand(
+            Command::new("test-rules")
+                .about("Run the [[tests]] entries of the configuration and fail on any mismatch"),
+        )
+        .subcommand(
+            Command::new("suggest-mappings")
+                .about("Suggest command mappings from commands that failed and were retried with another tool")
+                .arg(
+                    Arg::new("min-count")
+                        .long("min-count")
+                        .value_name("N")
+                        .help("Only show mappings seen at least N times (default: 2)")
+                        .value_parser(clap::value_parser!(usize)),
+                )
+                .arg(
+                    Arg::new("apply")
+                        .long("apply")
+                        .value_name("LIST")
+                        .help("Append suggestions to the project config: 'all' or numbers such as 1,3"),
+                ),
+        )
+        .subcommand(
+            Command::new("lint")
+                .about("Check command rules for cycles, shadowing and missing binaries")
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .help("Print lint results as JSON")
+                        .action(clap::ArgAction::SetTrue),
+                ),
+        )
         .get_matches();
 
     let config_path = matches.get_one::<String>("config")
         .expect("config argument has default value");
     let replace_mode = matches.get_flag("replace");
 
-    if matches.get_flag("hook") {
+    if let Some(explain) = matches.subcommand_matches("explain") {
+        let command = explain.get_one::<String>("command")
+            .expect("command argument is required");
+        let cwd = explain.get_one::<String>("cwd").map(|s| s.as_str());
+        crate::explain::run_explain(config_path, command, cwd, replace_mode)
+    } else if matches.subcommand_matches("test-rules").is_some() {
+        if !crate::rule_tests::run_rule_tests(config_path, replace_mode)? {
+            std::process::exit(1);
+        }
+        Ok(())
+    } else if let Some(suggest) = matches.subcommand_matches("suggest-mappings") {
+        let min_count = suggest.get_one::<usize>("min-count").copied().unwrap_or(2);
+        let apply = suggest.get_one::<String>("apply").map(|s| s.as_str());
+        crate::suggest::run_suggest_mappings(config_path, min_count, apply)
+    } else if let Some(lint) = matches.subcommand_matches("lint") {
+        if !crate::lint::run_lint(config_path, lint.get_flag("json"))? {
+            std::process::exit(1);
+        }
+        Ok(())
+    } else if matches.get_flag("hook") {
         run_as_hook(config_path, replace_mode)
     } else if matches.get_flag("install") {
         run_smart_installation(config_path)
     } else if matches.get_flag("uninstall") {
         crate::installer::uninstall_claude_hooks()
+    } else if matches.get_flag("validate") {
+        if !crate::doctor::run_validation(config_path, matches.get_flag("json"))? {
+            std::process::exit(1);
+        }
+        Ok(())
     } else if matches.get_flag("history") {
         let limit = matches.get_one::<usize>("limit").copied();
         let session_id = matches.get_one::<String>("session").map(|s| s.to_string());
         let failures_only = matches.get_flag("failures");
         let pattern = matches.get_one::<String>("pattern").map(|s| s.to_string());
+        let adoption = matches.get_flag("adoption");
 
-        show_command_history(config_path, limit, session_id, failures_only, pattern)
+        show_command_history(config_path, limit, session_id, failures_only, pattern, adoption)
     } else {
         println!("Claude Hook Advisor v{}", env!("CARGO_PKG_VERSION"));
         println!();
         println!("Installation:");
         println!("  --install                 Install Claude Hook Advisor: configure hooks and create/update config file");
+        println!("  --validate                Check installation and configuration health");
+        println!("  --json                    Print --validate results as JSON");
         println!();
         println!("Command Mapping:");
         println!("  --hook                    Run as a Claude Code hook");
+        println!("  explain --command <CMD>   Show what the hook would do with a command (dry run)");
+        println!("          [--cwd <DIR>]     Evaluate as if Claude were working in DIR");
+        println!("  test-rules                Run the [[tests]] entries of the config file");
+        println!("  lint [--json]             Check command rules for cycles, shadowing and missing binaries");
         println!();
         println!("Command History:");
         println!("  --history                 View command history");
@@ -114,6 +218,10 @@ pub fn run_cli() -> Result<()> {
         println!("  --session <ID>            Filter by session ID");
         println!("  --failures                Show only failed commands");
         println!("  --pattern <PATTERN>       Filter by command pattern");
+        println!("  --adoption                Show per-rule adoption of suggested replacements");
+        println!("  suggest-mappings          Suggest mappings from failed commands Claude retried");
+        println!("          [--min-count <N>] Only show mappings seen at least N times (default: 2)");
+        println!("          [--apply <LIST>]  Append suggestions to the config ('all' or e.g. 1,3)");
         println!();
         println!("Configuration:");
         println!("  -c, --config <FILE>       Path to config file [default: .claude-hook-advisor.toml]");
@@ -127,11 +235,12 @@ pub fn run_cli() -> Result<()> {
 /// Shows command history from the SQLite database.
 ///
 /// # Arguments
-/// * `config_path` - Path to configuration file (to get history DB path)
+/// * `config_path` - Project configuration file name (to get history DB path)
 /// * `limit` - Maximum number of records to show
 /// * `session_id` - Optional session ID filter
 /// * `failures_only` - Whether to show only failed commands
 /// * `pattern` - Optional command pattern filter
+/// * `adoption` - Show per-rule adoption rates instead of individual commands
 ///
 /// # Returns
 /// * `Ok(())` - History displayed successfully
@@ -142,11 +251,12 @@ fn show_command_history(
     session_id: Option<String>,
     failures_only: bool,
     pattern: Option<String>,
+    adoption: bool,
 ) -> Result<()> {
     use crate::history;
 
     // Load config to get history database path
-    let config = crate::config::load_config(config_path)
+    let config = crate::config::load_layered_config(config_path, None)
         .context("Failed to load configuration")?;
 
     // Get history configuration
@@ -181,6 +291,16 @@ fn show_command_history(
     let conn = history::init_database(&log_path)
         .context("Failed to open command history database")?;
 
+    if adoption {
+        // Every command of the session is needed to see what followed a suggestion
+        let query = history::HistoryQuery { session_id, ..Default::default() };
+        let mut records = history::query_history(&conn, &query)
+            .context("Failed to query command history")?;
+        records.reverse();
+        print_rule_adoption(&history::rule_adoption(&records));
+        return Ok(());
+    }
+
     // Build query
     let query = history::HistoryQuery {
         limit: Some(limit.unwrap_or(20)),
@@ -209,6 +329,8 @@ fn show_command_history(
         // Display status based on status field
         let status_str = match record.status.as_str() {
             "success" => "✓".to_string(),
+            "mapped" => "↪ MAPPED (not run)".to_string(),
+            "denied" => "⛔ DENIED (not run)".to_string(),
             "pending" => {
                 // Pending means failed (PostToolUse never fired)
                 if let Some(code) = record.exit_code {
@@ -240,10 +362,18 @@ fn show_command_history(
             }
         }
 
+        if let Some(rule_id) = record.rule_id {
+            println!("  Rule:    {}", rule_id);
+        }
+
+        if let Some(suggested) = record.suggested_command {
+            println!("  Suggested: {}", suggested);
+        }
+
         println!("  Session: {}", record.session_id);
 
-        // Show status if not standard success/pending
-        if record.status != "success" && record.status != "pending" {
+        // Show status if not a known status
+        if !matches!(record.status.as_str(), "success" | "pending" | "mapped" | "denied") {
             println!("  Status:  {}", record.status);
         }
 
@@ -253,6 +383,31 @@ fn show_command_history(
     Ok(())
 }
 
+/// Prints per-rule adoption of suggested replacements.
+fn print_rule_adoption(adoption: &[crate::history::RuleAdoption]) {
+    if adoption.is_empty() {
+        println!("No rule matches recorded yet.");
+        return;
+    }
+
+    println!("Rule Adoption ({} rules)", adoption.len());
+    println!("{}", "=".repeat(80));
+    println!("{:<32} {:>6} {:>10} {:>8} {:>9}", "Rule", "Hits", "Suggested", "Adopted", "Adoption");
+
+    for rule in adoption {
+        let rate = rule
+            .rate()
+            .map_or_else(|| "-".to_string(), |rate| format!("{:.0}%", rate * 100.0));
+        println!(
+            "{:<32} {:>6} {:>10} {:>8} {:>9}",
+            rule.rule_id, rule.hits, rule.suggested, rule.adopted, rate
+        );
+    }
+
+    println!();
+    println!("Adoption: share of suggestions whose next command in the session was the suggested replacement.");
+}
+
 /// Expands tilde (~) in file paths to the user's home directory
 fn expand_tilde_path(path: &str) -> Result<std::path::PathBuf> {
     if path.starts_with("~/") {
@@ -378,7 +533,7 @@ fn create_smart_config(config_path: &str) -> Result<()> {
         commands,
         semantic_directories: std::collections::HashMap::new(), // Empty - will be comments only
         command_history: None, // Will be added as commented example
-        security_pattern_overrides: std::collections::HashMap::new(), // Empty - defaults are enabled
+        ..Default::default() // Security overrides and rule tables empty - defaults are enabled
     };
     
     // Generate TOML content
@@ -519,7 +674,6 @@ fn get_commands_for_project_type(project_type: &str) -> std::collections::HashMa
     // Add common safety and modern tool mappings for all project types
     commands.insert("curl".to_string(), "curl -L".to_string());
     commands.insert("rm".to_string(), "trash".to_string());
-    commands.insert("rm -rf".to_string(), "echo 'Use trash command for safety'".to_string());
     
     commands
 }
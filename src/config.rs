@@ -1,53 +1,338 @@
 //! Configuration loading and management
+//!
+//! Configuration is assembled from up to three layers, applied in order:
+//! 1. User-global: `~/.config/claude-hook-advisor/config.toml`
+//! 2. Project: the nearest `.claude-hook-advisor.toml`, searched upwards from the hook's cwd
+//! 3. Local override: `.claude-hook-advisor.local.toml` next to the project file (uncommitted)
+//!
+//! Later layers are deep-merged over earlier ones.
 
+use crate::rules::validate_rules;
 use crate::types::Config;
 use anyhow::{Context, Result};
-use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-/// Loads configuration from a TOML file.
-/// 
-/// If the config file doesn't exist, returns an empty configuration and logs
-/// a warning to stderr. This allows the tool to work gracefully without config.
-/// 
+/// A configuration file that contributes to the merged configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigLayer {
+    /// Layer name: "global", "project" or "local"
+    pub kind: &'static str,
+    pub path: PathBuf,
+}
+
+/// Loads the layered configuration for a given working directory.
+///
+/// Discovers the global, project and local layers (see module docs) and
+/// deep-merges them into a single `Config`. If no layer exists, returns an
+/// empty configuration and logs a warning to stderr.
+///
 /// # Arguments
-/// * `config_path` - Path to the .claude-hook-advisor.toml file
-/// 
+/// * `config_path` - Project config file name (searched upwards) or absolute path
+/// * `cwd` - Working directory reported by the hook; falls back to the process cwd
+///
 /// # Returns
-/// * `Ok(Config)` - Loaded configuration or empty config if file not found
-/// * `Err` - If file exists but cannot be read or parsed
-pub fn load_config(config_path: &str) -> Result<Config> {
-    if !Path::new(config_path).exists() {
-        // Log warning to stderr when config file is not found
+/// * `Ok(Config)` - Merged configuration
+/// * `Err` - If any discovered layer cannot be read or parsed
+pub fn load_layered_config(config_path: &str, cwd: Option<&str>) -> Result<Config> {
+    let layers = discover_config_layers(config_path, cwd);
+
+    if layers.is_empty() {
         eprintln!("Warning: Config file '{config_path}' not found. No command mappings will be applied.");
-        return Ok(Config {
-            commands: HashMap::new(),
-            semantic_directories: HashMap::new(),
-            command_history: None,
-            security_pattern_overrides: HashMap::new(),
-        });
+        return Ok(Config::default());
+    }
+
+    load_layers(&layers)
+}
+
+/// Finds the configuration layers that exist for a given working directory.
+///
+/// Layers are returned in merge order (global, project, local).
+pub fn discover_config_layers(config_path: &str, cwd: Option<&str>) -> Vec<ConfigLayer> {
+    let start_dir = match cwd {
+        Some(dir) => PathBuf::from(dir),
+        None => std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+    };
+
+    discover_layers_from(global_config_path(), config_path, &start_dir)
+}
+
+/// Returns the path of the user-global configuration file, if HOME is set.
+pub fn global_config_path() -> Option<PathBuf> {
+    let home = std::env::var("HOME").ok()?;
+    Some(PathBuf::from(home).join(".config/claude-hook-advisor/config.toml"))
+}
+
+/// Discovers layers relative to an explicit global path and start directory.
+fn discover_layers_from(global: Option<PathBuf>, config_path: &str, start_dir: &Path) -> Vec<ConfigLayer> {
+    let mut layers = Vec::new();
+
+    if let Some(global) = global.filter(|path| path.is_file()) {
+        layers.push(ConfigLayer { kind: "global", path: global });
+    }
+
+    let project = find_project_config(config_path, start_dir);
+    let local = match &project {
+        Some(path) => local_override_path(path),
+        None => local_override_path(&start_dir.join(config_path)),
+    };
+
+    if let Some(path) = project {
+        layers.push(ConfigLayer { kind: "project", path });
+    }
+
+    if local.is_file() {
+        layers.push(ConfigLayer { kind: "local", path: local });
+    }
+
+    layers
+}
+
+/// Finds the project config by walking up from `start_dir`.
+///
+/// Absolute paths are used as-is and never searched for.
+fn find_project_config(config_path: &str, start_dir: &Path) -> Option<PathBuf> {
+    let path = Path::new(config_path);
+    if path.is_absolute() {
+        return path.is_file().then(|| path.to_path_buf());
     }
 
-    let content = fs::read_to_string(config_path)
-        .with_context(|| format!("Failed to read config file: {config_path}"))?;
+    start_dir
+        .ancestors()
+        .map(|dir| dir.join(path))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Derives the local override path: `name.toml` becomes `name.local.toml`.
+fn local_override_path(project_path: &Path) -> PathBuf {
+    let file_name = project_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let local_name = match file_name.strip_suffix(".toml") {
+        Some(stem) => format!("{stem}.local.toml"),
+        None => format!("{file_name}.local"),
+    };
+
+    project_path.with_file_name(local_name)
+}
+
+/// Reads, deep-merges and deserializes the given layers.
+fn load_layers(layers: &[ConfigLayer]) -> Result<Config> {
+    let mut merged = toml::Table::new();
+
+    for layer in layers {
+        let content = fs::read_to_string(&layer.path)
+            .with_context(|| format!("Failed to read config file: {}", layer.path.display()))?;
 
-    let config: Config = toml::from_str(&content)
-        .with_context(|| format!("Failed to parse config file: {config_path}"))?;
+        let table: toml::Table = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse config file: {}", layer.path.display()))?;
+
+        merge_tables(&mut merged, table);
+    }
+    remove_disabled_entries(&mut merged);
+
+    let config: Config = toml::Value::Table(merged)
+        .try_into()
+        .context("Failed to parse merged configuration")?;
+
+    validate_rules(&config)?;
 
     Ok(config)
 }
 
+/// Keys that identify an entry of a `[[...]]` table, in order of preference.
+///
+/// Rule ids default to the pattern, so `pattern` and `match` come last.
+const ENTRY_KEYS: &[&str] = &["id", "rule_name", "name", "pattern", "match"];
+
+/// Deep-merges `overlay` into `base`.
+///
+/// Tables are merged key by key and scalars are replaced. Array entries that
+/// are tables with an identifying key (see `ENTRY_KEYS`) replace the entry with
+/// the same key, so a later layer can override a `[[command_rules]]` or
+/// `[[security_patterns]]` entry; other entries are appended.
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(incoming)) => {
+                merge_tables(existing, incoming);
+            }
+            (Some(toml::Value::Array(existing)), toml::Value::Array(incoming)) => {
+                for entry in incoming {
+                    let position = entry_key(&entry)
+                        .and_then(|key| existing.iter().position(|other| entry_key(other) == Some(key)));
+                    match position {
+                        Some(index) => existing[index] = entry,
+                        None => existing.push(entry),
+                    }
+                }
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Returns the value that identifies an array entry across layers.
+fn entry_key(entry: &toml::Value) -> Option<&str> {
+    let table = entry.as_table()?;
+    ENTRY_KEYS.iter().find_map(|key| table.get(*key)?.as_str())
+}
+
+/// Drops `[[...]]` entries marked `enabled = false`, which is how a later
+/// layer disables an entry from an earlier one.
+fn remove_disabled_entries(table: &mut toml::Table) {
+    for (_, value) in table.iter_mut() {
+        if let toml::Value::Array(entries) = value {
+            entries.retain(|entry| {
+                entry.as_table().and_then(|entry| entry.get("enabled")).and_then(toml::Value::as_bool) != Some(false)
+            });
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{PatternOverride, Severity};
+    use tempfile::tempdir;
 
     #[test]
     fn test_config_loading_missing_file() {
-        // Test loading non-existent config file
-        let result = load_config("non-existent-file.toml");
+        // Test loading when no config layer exists
+        let temp_dir = tempdir().unwrap();
+        let cwd = temp_dir.path().to_str().unwrap();
+        let result = load_layered_config("non-existent-file.toml", Some(cwd));
         assert!(result.is_ok()); // Should return empty config
         let config = result.unwrap();
         assert!(config.commands.is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_local_override_path() {
+        let path = local_override_path(Path::new("/repo/.claude-hook-advisor.toml"));
+        assert_eq!(path, PathBuf::from("/repo/.claude-hook-advisor.local.toml"));
+    }
+
+    #[test]
+    fn test_discovers_project_config_in_parent_directory() {
+        let temp_dir = tempdir().unwrap();
+        let nested = temp_dir.path().join("web/src");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(temp_dir.path().join(".claude-hook-advisor.toml"), "[commands]\nnpm = \"bun\"\n").unwrap();
+
+        let layers = discover_layers_from(None, ".claude-hook-advisor.toml", &nested);
+
+        assert_eq!(layers.len(), 1);
+        assert_eq!(layers[0].kind, "project");
+        assert_eq!(layers[0].path, temp_dir.path().join(".claude-hook-advisor.toml"));
+    }
+
+    #[test]
+    fn test_layers_are_deep_merged() {
+        let temp_dir = tempdir().unwrap();
+        let global = temp_dir.path().join("global.toml");
+        let project_dir = temp_dir.path().join("project");
+        fs::create_dir_all(&project_dir).unwrap();
+
+        fs::write(&global, r#"
+[commands]
+npm = "pnpm"
+curl = "curl -L"
+
+[security_pattern_overrides]
+swift_force_unwrap = false
+"#).unwrap();
+        fs::write(project_dir.join(".claude-hook-advisor.toml"), r#"
+[commands]
+npm = "bun"
+
+[semantic_directories]
+docs = "~/docs"
+"#).unwrap();
+        fs::write(project_dir.join(".claude-hook-advisor.local.toml"), r#"
+[commands]
+yarn = "bun"
+
+[security_pattern_overrides]
+rust_unsafe_block = "info"
+"#).unwrap();
+
+        let layers = discover_layers_from(Some(global), ".claude-hook-advisor.toml", &project_dir);
+        let kinds: Vec<&str> = layers.iter().map(|layer| layer.kind).collect();
+        assert_eq!(kinds, vec!["global", "project", "local"]);
+
+        let config = load_layers(&layers).unwrap();
+        assert_eq!(config.commands.get("npm").unwrap(), "bun");
+        assert_eq!(config.commands.get("curl").unwrap(), "curl -L");
+        assert_eq!(config.commands.get("yarn").unwrap(), "bun");
+        assert_eq!(config.semantic_directories.get("docs").unwrap(), "~/docs");
+        assert_eq!(config.security_pattern_overrides.get("swift_force_unwrap"), Some(&PatternOverride::Enabled(false)));
+        assert_eq!(config.security_pattern_overrides.get("rust_unsafe_block"), Some(&PatternOverride::Severity(Severity::Info)));
+    }
+
+    #[test]
+    fn test_merge_tables_concatenates_arrays() {
+        let mut base: toml::Table = toml::from_str("items = [1, 2]\nname = \"a\"").unwrap();
+        let overlay: toml::Table = toml::from_str("items = [3]\nname = \"b\"").unwrap();
+
+        merge_tables(&mut base, overlay);
+
+        assert_eq!(base["items"].as_array().unwrap().len(), 3);
+        assert_eq!(base["name"].as_str(), Some("b"));
+    }
+
+    #[test]
+    fn test_later_layers_override_rule_entries() {
+        let temp_dir = tempdir().unwrap();
+        let global = temp_dir.path().join("global.toml");
+        let project_dir = temp_dir.path().join("project");
+        fs::create_dir_all(&project_dir).unwrap();
+
+        fs::write(&global, r#"
+[[command_rules]]
+pattern = "npm"
+replacement = "pnpm"
+
+[[command_rules]]
+id = "no-wget"
+pattern = "wget"
+replacement = "curl -LO"
+action = "deny"
+
+[[security_patterns]]
+rule_name = "todo_marker"
+content_substrings = ["TODO"]
+reminder = "Resolve TODOs"
+"#).unwrap();
+        fs::write(project_dir.join(".claude-hook-advisor.toml"), r#"
+[[command_rules]]
+pattern = "npm"
+replacement = "bun"
+
+[[command_rules]]
+id = "no-wget"
+enabled = false
+
+[[security_patterns]]
+rule_name = "todo_marker"
+content_substrings = ["TODO", "FIXME"]
+reminder = "Resolve TODOs and FIXMEs"
+"#).unwrap();
+
+        let layers = discover_layers_from(Some(global), ".claude-hook-advisor.toml", &project_dir);
+        let config = load_layers(&layers).unwrap();
+
+        let rules: Vec<(&str, &str)> = config
+            .command_rules
+            .iter()
+            .map(|rule| (rule.pattern.as_str(), rule.replacement.as_str()))
+            .collect();
+        assert_eq!(rules, vec![("npm", "bun")]);
+        assert_eq!(config.security_patterns.len(), 1);
+        assert_eq!(config.security_patterns[0].content_substrings, vec!["TODO", "FIXME"]);
+    }
+}
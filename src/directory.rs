@@ -133,10 +133,8 @@ mod tests {
         semantic_directories.insert("project_docs".to_string(), "~/Documents/Documentation/project".to_string());
 
         Config {
-            commands: HashMap::new(),
             semantic_directories,
-            command_history: None,
-            security_pattern_overrides: HashMap::new(),
+            ..Default::default()
         }
     }
 
@@ -180,7 +178,7 @@ mod tests {
         
         // The important behavioral test: ensure we're not doing fuzzy matching
         let no_fuzzy_match = "check documentation folder";
-        let results2 = detect_directory_references(&config, &no_fuzzy_match);
+        let results2 = detect_directory_references(&config, no_fuzzy_match);
         assert_eq!(results2.len(), 0, "Should not fuzzy-match 'documentation' to 'docs'");
     }
 }
\ No newline at end of file
@@ -0,0 +1,407 @@
+//! Installation and configuration diagnostics (`--validate`)
+
+use crate::config::{discover_config_layers, load_layered_config};
+use crate::directory::resolve_directory;
+use crate::history;
+use crate::hooks::expand_tilde;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Hook events that must be registered for full functionality
+const REQUIRED_HOOK_EVENTS: &[&str] = &["PreToolUse", "UserPromptSubmit", "PostToolUse"];
+
+/// Claude Code settings files that may contain our hooks
+const SETTINGS_FILES: &[&str] = &[".claude/settings.json", ".claude/settings.local.json"];
+
+/// Directory, relative to the project or home directory, that holds plugins
+const PLUGINS_DIR: &str = ".claude/plugins";
+
+/// Hook definitions inside a plugin directory
+const PLUGIN_HOOKS_FILE: &str = "hooks/hooks.json";
+
+/// Result of a single diagnostic check
+#[derive(Debug, Serialize)]
+pub struct CheckResult {
+    pub name: String,
+    pub passed: bool,
+    pub message: String,
+}
+
+impl CheckResult {
+    fn pass(name: impl Into<String>, message: impl Into<String>) -> Self {
+        CheckResult { name: name.into(), passed: true, message: message.into() }
+    }
+
+    fn fail(name: impl Into<String>, message: impl Into<String>) -> Self {
+        CheckResult { name: name.into(), passed: false, message: message.into() }
+    }
+}
+
+/// Full diagnostics report, serialized as-is for `--json`
+#[derive(Debug, Serialize)]
+pub struct DoctorReport {
+    pub ok: bool,
+    pub checks: Vec<CheckResult>,
+}
+
+/// Runs all diagnostic checks and prints the report.
+///
+/// # Arguments
+/// * `config_path` - Project configuration file name
+/// * `json` - Print machine-readable JSON instead of ✓/✗ lines
+///
+/// # Returns
+/// * `Ok(true)` - All checks passed
+/// * `Ok(false)` - At least one check failed
+/// * `Err` - If the report cannot be serialized
+pub fn run_validation(config_path: &str, json: bool) -> Result<bool> {
+    let report = build_report(config_path);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+
+    Ok(report.ok)
+}
+
+/// Collects the results of every diagnostic check.
+fn build_report(config_path: &str) -> DoctorReport {
+    let mut checks = vec![check_binary_on_path()];
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    checks.extend(check_hooks_registered(Path::new("."), home.as_deref()));
+    checks.extend(check_config(config_path));
+
+    let ok = checks.iter().all(|check| check.passed);
+    DoctorReport { ok, checks }
+}
+
+fn print_report(report: &DoctorReport) {
+    println!("🩺 Claude Hook Advisor - Validation");
+    println!("===================================");
+
+    for check in &report.checks {
+        let mark = if check.passed { "✓" } else { "✗" };
+        println!("{mark} {}: {}", check.name, check.message);
+    }
+
+    println!();
+    if report.ok {
+        println!("All checks passed.");
+    } else {
+        let failed = report.checks.iter().filter(|check| !check.passed).count();
+        println!("{failed} check(s) need attention.");
+    }
+}
+
+/// Checks that the binary can be found on PATH, as hook commands rely on it.
+fn check_binary_on_path() -> CheckResult {
+    let name = "Binary on PATH";
+    match which::which(env!("CARGO_PKG_NAME")) {
+        Ok(path) => CheckResult::pass(name, path.display().to_string()),
+        Err(_) => CheckResult::fail(
+            name,
+            format!("{} not found on PATH (run: cargo install claude-hook-advisor)", env!("CARGO_PKG_NAME")),
+        ),
+    }
+}
+
+/// Checks that our hook is registered for every required event.
+///
+/// The project's shared and local settings files, the user settings file and
+/// the hooks of installed plugins (in the project, in `~/.claude/plugins` and
+/// the running plugin's `$CLAUDE_PLUGIN_ROOT`) are all considered.
+fn check_hooks_registered(project_dir: &Path, home_dir: Option<&Path>) -> Vec<CheckResult> {
+    let mut registered: Vec<(String, String)> = Vec::new();
+    let mut errors: Vec<CheckResult> = Vec::new();
+
+    for (source, path) in hook_sources(project_dir, home_dir) {
+        if !path.exists() {
+            continue;
+        }
+
+        match read_registered_events(&path) {
+            Ok(events) => registered.extend(events.into_iter().map(|event| (event, source.clone()))),
+            Err(err) => errors.push(CheckResult::fail(format!("Settings file {source}"), format!("{err:#}"))),
+        }
+    }
+
+    let mut checks = errors;
+    for event in REQUIRED_HOOK_EVENTS {
+        let name = format!("Hook registered: {event}");
+        let sources: Vec<&str> = registered
+            .iter()
+            .filter(|(registered_event, _)| registered_event == event)
+            .map(|(_, source)| source.as_str())
+            .collect();
+        if sources.is_empty() {
+            checks.push(CheckResult::fail(
+                name,
+                "not found in .claude/settings*.json, ~/.claude/settings.json or an installed plugin \
+                 (run: claude-hook-advisor --install)",
+            ));
+        } else {
+            checks.push(CheckResult::pass(name, format!("found in {}", sources.join(", "))));
+        }
+    }
+
+    checks
+}
+
+/// Returns the files that may register our hooks, labelled for messages.
+fn hook_sources(project_dir: &Path, home_dir: Option<&Path>) -> Vec<(String, PathBuf)> {
+    let mut sources: Vec<(String, PathBuf)> = SETTINGS_FILES
+        .iter()
+        .map(|settings_file| (settings_file.to_string(), project_dir.join(settings_file)))
+        .collect();
+    if let Some(home_dir) = home_dir {
+        sources.push(("~/.claude/settings.json".to_string(), home_dir.join(".claude/settings.json")));
+    }
+
+    let mut plugin_dirs: Vec<PathBuf> = std::env::var_os("CLAUDE_PLUGIN_ROOT").map(PathBuf::from).into_iter().collect();
+    for plugins_dir in [Some(project_dir), home_dir].into_iter().flatten().map(|dir| dir.join(PLUGINS_DIR)) {
+        let Ok(entries) = fs::read_dir(&plugins_dir) else {
+            continue;
+        };
+        let mut dirs: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+        dirs.sort();
+        plugin_dirs.extend(dirs);
+    }
+    for plugin_dir in plugin_dirs {
+        let path = plugin_dir.join(PLUGIN_HOOKS_FILE);
+        if !sources.iter().any(|(_, existing)| *existing == path) {
+            sources.push((path.display().to_string(), path));
+        }
+    }
+
+    sources
+}
+
+/// Returns the hook events in a settings file that invoke claude-hook-advisor.
+fn read_registered_events(settings_path: &Path) -> Result<Vec<String>> {
+    let content = fs::read_to_string(settings_path)
+        .with_context(|| format!("Failed to read {}", settings_path.display()))?;
+
+    if content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let settings: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", settings_path.display()))?;
+
+    let Some(hooks) = settings.get("hooks").and_then(|h| h.as_object()) else {
+        return Ok(Vec::new());
+    };
+
+    let events = hooks
+        .iter()
+        .filter(|(_, groups)| {
+            groups.as_array().into_iter().flatten().any(|group| {
+                group
+                    .get("hooks")
+                    .and_then(|h| h.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|hook| hook.get("command").and_then(|c| c.as_str()))
+                    .any(|command| command.contains("claude-hook-advisor"))
+            })
+        })
+        .map(|(event, _)| event.clone())
+        .collect();
+
+    Ok(events)
+}
+
+/// Checks that the configuration parses, then validates what it points at.
+fn check_config(config_path: &str) -> Vec<CheckResult> {
+    let layers = discover_config_layers(config_path, None);
+    let layer_list = layers
+        .iter()
+        .map(|layer| format!("{} ({})", layer.path.display(), layer.kind))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let config = match load_layered_config(config_path, None) {
+        Ok(config) => config,
+        Err(err) => return vec![CheckResult::fail("Config parses", format!("{err:#}"))],
+    };
+
+    let mut checks = Vec::new();
+    if layers.is_empty() {
+        checks.push(CheckResult::fail(
+            "Config parses",
+            format!("no {config_path} found (run: claude-hook-advisor --install)"),
+        ));
+    } else {
+        checks.push(CheckResult::pass("Config parses", layer_list));
+    }
+
+    let mut aliases: Vec<&String> = config.semantic_directories.keys().collect();
+    aliases.sort();
+    for alias in aliases {
+        let name = format!("Directory alias '{alias}'");
+        match resolve_directory(&config, alias) {
+            Ok(resolution) => checks.push(CheckResult::pass(name, resolution.canonical_path)),
+            Err(_) => checks.push(CheckResult::fail(
+                name,
+                format!("path does not exist: {}", config.semantic_directories[alias]),
+            )),
+        }
+    }
+
+    checks.push(check_history_writable(config.command_history.as_ref()));
+    checks
+}
+
+/// Checks that the history database can be opened and written to.
+fn check_history_writable(history_config: Option<&crate::types::CommandHistoryConfig>) -> CheckResult {
+    let name = "History database writable";
+
+    let Some(history_config) = history_config.filter(|cfg| cfg.enabled) else {
+        return CheckResult::pass(name, "command history disabled (skipped)");
+    };
+
+    let db_path = match expand_tilde(&history_config.log_file) {
+        Ok(path) => path,
+        Err(err) => return CheckResult::fail(name, format!("{err:#}")),
+    };
+
+    let result = history::init_database(&db_path).and_then(|conn| {
+        // Write inside a transaction that is rolled back so the check leaves no trace
+        conn.execute_batch(
+            "BEGIN IMMEDIATE;
+             INSERT INTO commands (timestamp, session_id, command) VALUES ('', 'validate', '');
+             ROLLBACK;",
+        )
+        .context("Failed to write to history database")
+    });
+
+    match result {
+        Ok(()) => CheckResult::pass(name, db_path.display().to_string()),
+        Err(err) => CheckResult::fail(name, format!("{}: {err:#}", db_path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CommandHistoryConfig;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_hooks_registered_across_settings_files() {
+        let temp_dir = tempdir().unwrap();
+        fs::create_dir_all(temp_dir.path().join(".claude")).unwrap();
+
+        let shared = serde_json::json!({
+            "hooks": {
+                "PreToolUse": [{
+                    "matcher": "Bash",
+                    "hooks": [{ "type": "command", "command": "claude-hook-advisor --hook" }]
+                }]
+            }
+        });
+        let local = serde_json::json!({
+            "hooks": {
+                "PostToolUse": [{
+                    "matcher": "Bash",
+                    "hooks": [{ "type": "command", "command": "/bin/claude-hook-advisor --hook" }]
+                }],
+                "UserPromptSubmit": [{
+                    "hooks": [{ "type": "command", "command": "some-other-tool" }]
+                }]
+            }
+        });
+        fs::write(temp_dir.path().join(".claude/settings.json"), shared.to_string()).unwrap();
+        fs::write(temp_dir.path().join(".claude/settings.local.json"), local.to_string()).unwrap();
+
+        let checks = check_hooks_registered(temp_dir.path(), None);
+        let status: Vec<(&str, bool)> = checks
+            .iter()
+            .map(|check| (check.name.as_str(), check.passed))
+            .collect();
+
+        assert_eq!(status, vec![
+            ("Hook registered: PreToolUse", true),
+            ("Hook registered: UserPromptSubmit", false),
+            ("Hook registered: PostToolUse", true),
+        ]);
+    }
+
+    #[test]
+    fn test_hooks_registered_reports_invalid_settings() {
+        let temp_dir = tempdir().unwrap();
+        fs::create_dir_all(temp_dir.path().join(".claude")).unwrap();
+        fs::write(temp_dir.path().join(".claude/settings.json"), "{ not json").unwrap();
+
+        let checks = check_hooks_registered(temp_dir.path(), None);
+        assert!(!checks[0].passed);
+        assert_eq!(checks[0].name, "Settings file .claude/settings.json");
+    }
+
+    #[test]
+    fn test_hooks_registered_by_plugin() {
+        let project = tempdir().unwrap();
+        let home = tempdir().unwrap();
+        let plugin_hooks = home.path().join(".claude/plugins/claude-hook-advisor/hooks");
+        fs::create_dir_all(&plugin_hooks).unwrap();
+        fs::copy(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("plugin/hooks/hooks.json"),
+            plugin_hooks.join("hooks.json"),
+        ).unwrap();
+
+        let checks = check_hooks_registered(project.path(), Some(home.path()));
+        assert_eq!(checks.len(), REQUIRED_HOOK_EVENTS.len());
+        for check in &checks {
+            assert!(check.passed, "{}: {}", check.name, check.message);
+            assert!(check.message.contains("claude-hook-advisor/hooks/hooks.json"), "{}", check.message);
+        }
+
+        // Hooks in the user settings file count as well
+        let settings = serde_json::json!({
+            "hooks": {
+                "PreToolUse": [{ "hooks": [{ "type": "command", "command": "claude-hook-advisor --hook" }] }]
+            }
+        });
+        let other_home = tempdir().unwrap();
+        fs::create_dir_all(other_home.path().join(".claude")).unwrap();
+        fs::write(other_home.path().join(".claude/settings.json"), settings.to_string()).unwrap();
+        let checks = check_hooks_registered(project.path(), Some(other_home.path()));
+        assert!(checks[0].passed);
+        assert_eq!(checks[0].message, "found in ~/.claude/settings.json");
+    }
+
+    #[test]
+    fn test_history_writable_check() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("history.db");
+        let history_config = CommandHistoryConfig {
+            enabled: true,
+            log_file: db_path.to_string_lossy().to_string(),
+        };
+
+        let check = check_history_writable(Some(&history_config));
+        assert!(check.passed, "{}", check.message);
+
+        // The probe row must not be left behind
+        let conn = history::init_database(&db_path).unwrap();
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM commands", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_report_json_shape() {
+        let report = DoctorReport {
+            ok: false,
+            checks: vec![CheckResult::fail("Config parses", "bad")],
+        };
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["ok"], false);
+        assert_eq!(json["checks"][0]["name"], "Config parses");
+        assert_eq!(json["checks"][0]["passed"], false);
+    }
+}
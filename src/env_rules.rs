@@ -0,0 +1,222 @@
+//! Environment variable prefixes for matching Bash commands (`[[env_rules]]`)
+//!
+//! Env rules do not change what a command does, only the environment it runs
+//! in: `CI=true` keeps test runners out of watch mode, `NO_COLOR=1` and
+//! `CARGO_TERM_COLOR=never` strip colour codes, `PAGER=cat` stops paging.
+//! Variables the command already sets are left alone, so applying the rules
+//! to their own output changes nothing.
+
+use crate::rules::{condition_holds, literal_regex};
+use crate::shell;
+use crate::types::{Config, EnvRule};
+use anyhow::Result;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// A variable added to a command by an env rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvAssignment {
+    pub rule_id: String,
+    pub name: String,
+    pub value: String,
+}
+
+/// Result of applying the env rules to a command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvInjection {
+    /// The command line with the variables prefixed
+    pub command: String,
+    /// Variables that were added, in order
+    pub applied: Vec<EnvAssignment>,
+}
+
+impl EnvInjection {
+    /// Returns the ids of the rules that added variables, without duplicates.
+    pub fn rule_ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = Vec::new();
+        for assignment in &self.applied {
+            if !ids.contains(&assignment.rule_id.as_str()) {
+                ids.push(&assignment.rule_id);
+            }
+        }
+        ids
+    }
+}
+
+/// Prefixes each simple command with the variables of the env rules it matches.
+///
+/// Every matching rule contributes; when two rules set the same variable the
+/// first one wins. A variable is skipped if the simple command already assigns
+/// it (`CI=1 npm test`, `env CI=1 npm test`) or an earlier `export` on the same
+/// line does. The assignments go right before the command word, after `sudo`
+/// (which resets the environment) and `time`, but before `nohup`. Under
+/// `sudo` they are passed through `env`, since sudo's default `env_reset`
+/// policy rejects `sudo NAME=value command`.
+///
+/// # Returns
+/// * `Ok(Some(injection))` - If at least one variable was added
+/// * `Ok(None)` - If no rule matches or every variable is already set
+/// * `Err` - If a rule pattern cannot be compiled
+pub fn apply_env_rules(config: &Config, command: &str, cwd: &Path) -> Result<Option<EnvInjection>> {
+    let mut rules: Vec<&EnvRule> = Vec::new();
+    for rule in &config.env_rules {
+        let applies = match &rule.when {
+            Some(condition) => condition_holds(condition, cwd)?,
+            None => true,
+        };
+        if applies && !rule.env.is_empty() {
+            rules.push(rule);
+        }
+    }
+
+    if rules.is_empty() {
+        return Ok(None);
+    }
+
+    let mut exported: HashSet<String> = HashSet::new();
+    let mut edits = Vec::new();
+    let mut applied = Vec::new();
+
+    for simple_command in shell::parse(command) {
+        let argv = simple_command.argv();
+        if argv.first() == Some(&"export") {
+            exported.extend(argv[1..].iter().filter_map(|word| assigned_name(word)));
+            continue;
+        }
+
+        let prefix = &simple_command.words[..simple_command.command_index];
+        let mut set: HashSet<String> = prefix.iter().filter_map(|word| assigned_name(&word.value)).collect();
+        set.extend(exported.iter().cloned());
+
+        let mut assignments = Vec::new();
+        for rule in &rules {
+            if !literal_regex(&rule.pattern)?.is_match(simple_command.command_text(command)) {
+                continue;
+            }
+            for (name, value) in &rule.env {
+                if set.insert(name.clone()) {
+                    assignments.push(EnvAssignment {
+                        rule_id: rule.id().to_string(),
+                        name: name.clone(),
+                        value: value.clone(),
+                    });
+                }
+            }
+        }
+
+        if assignments.is_empty() {
+            continue;
+        }
+
+        // `nohup` would run `NAME=value` as its command, so go in front of it
+        let position = prefix
+            .iter()
+            .find(|word| word.value == "nohup")
+            .map_or(simple_command.command_start, |word| word.start);
+        let before: Vec<&str> = prefix
+            .iter()
+            .take_while(|word| word.start < position)
+            .map(|word| word.value.as_str())
+            .collect();
+        let needs_env = before
+            .iter()
+            .rposition(|word| *word == "sudo")
+            .is_some_and(|sudo| !before[sudo..].contains(&"env"));
+        let mut words: Vec<String> = assignments
+            .iter()
+            .map(|assignment| format!("{}={} ", assignment.name, shell::quote(&assignment.value)))
+            .collect();
+        if needs_env {
+            words.insert(0, "env ".to_string());
+        }
+        edits.push((position..position, words.concat()));
+        applied.extend(assignments);
+    }
+
+    if edits.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(EnvInjection { command: shell::splice(command, &edits), applied }))
+}
+
+/// Returns the variable name of a `NAME=value` word.
+fn assigned_name(word: &str) -> Option<String> {
+    if !shell::is_assignment(word) {
+        return None;
+    }
+    word.split_once('=').map(|(name, _)| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        toml::from_str(r#"
+[[env_rules]]
+id = "ci"
+pattern = "npm test"
+env = { CI = "true" }
+
+[[env_rules]]
+pattern = "cargo"
+env = { CARGO_TERM_COLOR = "never", NO_COLOR = "1" }
+
+[[env_rules]]
+id = "pager"
+pattern = "git log"
+env = { PAGER = "less -R" }
+"#).unwrap()
+    }
+
+    fn apply(command: &str) -> Option<String> {
+        apply_env_rules(&config(), command, Path::new("/tmp"))
+            .unwrap()
+            .map(|injection| injection.command)
+    }
+
+    #[test]
+    fn test_prefixes_matching_commands() {
+        assert_eq!(apply("npm test").as_deref(), Some("CI=true npm test"));
+        assert_eq!(apply("cargo build --release").as_deref(), Some("CARGO_TERM_COLOR=never NO_COLOR=1 cargo build --release"));
+        assert_eq!(apply("cd web && npm test -- --watch").as_deref(), Some("cd web && CI=true npm test -- --watch"));
+        assert_eq!(apply("git log -5").as_deref(), Some("PAGER='less -R' git log -5"));
+        assert_eq!(apply("sudo npm test").as_deref(), Some("sudo env CI=true npm test"));
+        assert_eq!(apply("sudo nohup npm test").as_deref(), Some("sudo env CI=true nohup npm test"));
+        assert_eq!(apply("sudo env FOO=1 npm test").as_deref(), Some("sudo env FOO=1 CI=true npm test"));
+        assert_eq!(apply("time npm test").as_deref(), Some("time CI=true npm test"));
+        assert_eq!(apply("nohup npm test").as_deref(), Some("CI=true nohup npm test"));
+        assert_eq!(apply("FOO=1 npm test").as_deref(), Some("FOO=1 CI=true npm test"));
+        assert_eq!(apply("npm install"), None);
+        assert_eq!(apply("ls -la"), None);
+    }
+
+    #[test]
+    fn test_is_idempotent() {
+        for command in ["npm test", "cargo test", "cd web && npm test | tail", "sudo nohup npm test", "sudo time npm test"] {
+            let once = apply(command).unwrap();
+            assert_eq!(apply(&once), None, "{once}");
+        }
+
+        assert_eq!(apply("CI=1 npm test"), None);
+        assert_eq!(apply("env CI=1 npm test"), None);
+        assert_eq!(apply("export CI=1 && npm test"), None);
+        assert_eq!(apply("NO_COLOR=0 cargo test").as_deref(), Some("NO_COLOR=0 CARGO_TERM_COLOR=never cargo test"));
+    }
+
+    #[test]
+    fn test_reports_applied_rules_and_conditions() {
+        let injection = apply_env_rules(&config(), "cargo fmt && npm test", Path::new("/tmp")).unwrap().unwrap();
+        assert_eq!(injection.rule_ids(), vec!["cargo", "ci"]);
+        assert_eq!(injection.applied.len(), 3);
+
+        let config: Config = toml::from_str(r#"
+[[env_rules]]
+pattern = "npm test"
+env = { CI = "true" }
+when = { file_exists = "no-such-file.json" }
+"#).unwrap();
+        assert_eq!(apply_env_rules(&config, "npm test", Path::new("/tmp")).unwrap(), None);
+    }
+}
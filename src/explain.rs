@@ -0,0 +1,140 @@
+//! Dry-run explanation of hook decisions (`explain` subcommand)
+
+use crate::config::load_layered_config;
+use crate::hooks::{evaluate_bash_command, BashEvaluation};
+use anyhow::Result;
+use std::fmt::Write;
+
+/// Explains what the PreToolUse hook would do with a Bash command.
+///
+/// Loads the configuration for `cwd` the same way the hook does, evaluates the
+/// command and prints the matched rules, the rewritten command and the exact
+/// hook JSON. Nothing is read from stdin or written to history.
+///
+/// # Arguments
+/// * `config_path` - Project configuration file name
+/// * `command` - The bash command to explain
+/// * `cwd` - Working directory to evaluate in; defaults to the process cwd
+/// * `replace_mode` - Evaluate as if the hook ran with `--replace`
+pub fn run_explain(config_path: &str, command: &str, cwd: Option<&str>, replace_mode: bool) -> Result<()> {
+    let config = load_layered_config(config_path, cwd)?;
+    let evaluation = evaluate_bash_command(&config, command, cwd, replace_mode, false)?;
+
+    print!("{}", format_explanation(command, cwd, &evaluation)?);
+    Ok(())
+}
+
+/// Formats the explanation printed by `run_explain`.
+fn format_explanation(command: &str, cwd: Option<&str>, evaluation: &BashEvaluation) -> Result<String> {
+    let mut out = String::new();
+    writeln!(out, "🔍 Claude Hook Advisor - Explain")?;
+    writeln!(out, "================================")?;
+    writeln!(out, "Command: {command}")?;
+    writeln!(out, "CWD:     {}", cwd.unwrap_or("(current directory)"))?;
+    writeln!(out)?;
+
+    if let Some(violation) = &evaluation.violation {
+        writeln!(out, "⛔ Dangerous command policy '{}' matched", violation.rule_name)?;
+        writeln!(out, "  {}", violation.reason)?;
+    } else if let Some(secret) = &evaluation.secret {
+        writeln!(out, "🔑 Secret detector '{}' matched (action: {})", secret.rule_name, secret.action.as_str())?;
+        writeln!(out, "  {}", secret.description)?;
+    } else if evaluation.interactive.is_none() && evaluation.mapping.is_none() && evaluation.env.is_none() {
+        writeln!(out, "No rule matches; the command runs unchanged.")?;
+    } else {
+        if let Some(interactive) = &evaluation.interactive {
+            writeln!(out, "⌨️ Interactive command detector '{}' matched (action: {})", interactive.rule_name, interactive.action.as_str())?;
+            writeln!(out, "  matched:   {}", interactive.command)?;
+            writeln!(out, "  {}", interactive.reason)?;
+        }
+        if let Some(mapping) = &evaluation.mapping {
+            for rule_match in &mapping.matches {
+                writeln!(out, "Rule '{}' (action: {})", rule_match.rule_id, rule_match.action.as_str())?;
+                writeln!(out, "  why:       {}", rule_match.why)?;
+                writeln!(out, "  matched:   {}", rule_match.original)?;
+                writeln!(out, "  rewritten: {}", rule_match.rewritten)?;
+            }
+        }
+        write_env(&mut out, evaluation)?;
+
+        let result = evaluation
+            .rewritten_command()
+            .or(evaluation.mapping.as_ref().map(|mapping| mapping.replacement.as_str()))
+            .or(evaluation.interactive.as_ref().and_then(|interactive| interactive.replacement.as_deref()));
+        if let Some(result) = result {
+            writeln!(out)?;
+            writeln!(out, "Result:  {result}")?;
+        }
+        if let Some(mapping) = &evaluation.mapping {
+            writeln!(out, "Action:  {}{}", mapping.action.as_str(), if evaluation.rewrite { " (rewritten in place)" } else { "" })?;
+        }
+    }
+
+    writeln!(out)?;
+    writeln!(out, "Hook output:")?;
+    match &evaluation.response {
+        Some(response) => writeln!(out, "{}", response.to_correct_json()?)?,
+        None => writeln!(out, "(none)")?,
+    }
+
+    Ok(out)
+}
+
+/// Lists the variables added by `[[env_rules]]`.
+fn write_env(out: &mut String, evaluation: &BashEvaluation) -> Result<()> {
+    if let Some(env) = &evaluation.env {
+        for assignment in &env.applied {
+            writeln!(out, "Env rule '{}' sets {}={}", assignment.rule_id, assignment.name, assignment.value)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Config;
+
+    fn explain(config: &Config, command: &str) -> String {
+        let evaluation = evaluate_bash_command(config, command, None, false, false).unwrap();
+        format_explanation(command, None, &evaluation).unwrap()
+    }
+
+    #[test]
+    fn test_explains_matched_rule() {
+        let config: Config = toml::from_str(r#"
+[[rewrite_rules]]
+id = "npm-run-to-bun"
+match = '^npm run (\w+)$'
+replace = 'bun run $1'
+"#).unwrap();
+
+        let output = explain(&config, "cd web && npm run build");
+        assert!(output.contains("Rule 'npm-run-to-bun' (action: block)"));
+        assert!(output.contains("why:       regex '^npm run (\\w+)$'"));
+        assert!(output.contains("matched:   npm run build"));
+        assert!(output.contains("Result:  cd web && bun run build"));
+        assert!(output.contains(r#""permissionDecision":"deny""#));
+    }
+
+    #[test]
+    fn test_explains_unmatched_and_blocked_commands() {
+        let config = Config::default();
+
+        let output = explain(&config, "ls -la");
+        assert!(output.contains("No rule matches"));
+        assert!(output.ends_with("Hook output:\n(none)\n"));
+
+        let output = explain(&config, "git reset --hard");
+        assert!(output.contains("Dangerous command policy 'git_reset_hard' matched"));
+
+        let config: Config = toml::from_str("[[env_rules]]\npattern = 'cargo'\nenv = { NO_COLOR = '1' }").unwrap();
+        let output = explain(&config, "cargo build");
+        assert!(output.contains("Env rule 'cargo' sets NO_COLOR=1"));
+        assert!(output.contains("Result:  NO_COLOR=1 cargo build"));
+
+        let output = explain(&config, "git commit --amend");
+        assert!(output.contains("Interactive command detector 'git_editor' matched (action: rewrite)"));
+        assert!(output.contains("Result:  GIT_EDITOR=true git commit --amend"));
+    }
+}
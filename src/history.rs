@@ -15,7 +15,11 @@ pub struct CommandRecord {
     pub cwd: Option<String>,
     pub was_replaced: bool,
     pub original_command: Option<String>,
-    pub status: String, // "pending", "success", "failed"
+    pub status: String, // "pending", "success", "failed", "mapped", "denied"
+    /// Rule (or dangerous command policy) that matched the command
+    pub rule_id: Option<String>,
+    /// Replacement suggested to Claude instead of the command
+    pub suggested_command: Option<String>,
 }
 
 /// Initialize the command history database
@@ -40,7 +44,9 @@ pub fn init_database(db_path: &PathBuf) -> Result<Connection> {
             cwd TEXT,
             was_replaced INTEGER NOT NULL DEFAULT 0,
             original_command TEXT,
-            status TEXT NOT NULL DEFAULT 'pending'
+            status TEXT NOT NULL DEFAULT 'pending',
+            rule_id TEXT,
+            suggested_command TEXT
         )",
         [],
     )
@@ -52,6 +58,8 @@ pub fn init_database(db_path: &PathBuf) -> Result<Connection> {
         "ALTER TABLE commands ADD COLUMN status TEXT NOT NULL DEFAULT 'pending'",
         [],
     );
+    let _ = conn.execute("ALTER TABLE commands ADD COLUMN rule_id TEXT", []);
+    let _ = conn.execute("ALTER TABLE commands ADD COLUMN suggested_command TEXT", []);
 
     // Create indexes for common queries
     conn.execute(
@@ -72,8 +80,8 @@ pub fn init_database(db_path: &PathBuf) -> Result<Connection> {
 /// Log a command to the history database
 pub fn log_command(conn: &Connection, record: &CommandRecord) -> Result<()> {
     conn.execute(
-        "INSERT INTO commands (timestamp, session_id, command, exit_code, cwd, was_replaced, original_command, status)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        "INSERT INTO commands (timestamp, session_id, command, exit_code, cwd, was_replaced, original_command, status, rule_id, suggested_command)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
         params![
             record.timestamp,
             record.session_id,
@@ -83,6 +91,8 @@ pub fn log_command(conn: &Connection, record: &CommandRecord) -> Result<()> {
             record.was_replaced as i32,
             record.original_command,
             record.status,
+            record.rule_id,
+            record.suggested_command,
         ],
     )
     .context("Failed to insert command into history")?;
@@ -103,7 +113,7 @@ pub struct HistoryQuery {
 pub fn query_history(conn: &Connection, query: &HistoryQuery) -> Result<Vec<CommandRecord>> {
     let mut sql = String::from(
         "SELECT timestamp, session_id, command, exit_code, cwd, was_replaced, original_command,
-                COALESCE(status, 'pending') as status
+                COALESCE(status, 'pending') as status, rule_id, suggested_command
          FROM commands WHERE 1=1"
     );
 
@@ -141,6 +151,8 @@ pub fn query_history(conn: &Connection, query: &HistoryQuery) -> Result<Vec<Comm
             was_replaced: row.get::<_, i32>(5)? != 0,
             original_command: row.get(6)?,
             status: row.get(7)?,
+            rule_id: row.get(8)?,
+            suggested_command: row.get(9)?,
         })
     })
     .context("Failed to execute query")?;
@@ -172,9 +184,80 @@ pub fn create_record(
         was_replaced,
         original_command: original_command.map(|s| s.to_string()),
         status: status.to_string(),
+        rule_id: None,
+        suggested_command: None,
     }
 }
 
+/// How often Claude adopted the replacements suggested by one rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleAdoption {
+    pub rule_id: String,
+    /// Commands the rule matched, whatever its action
+    pub hits: usize,
+    /// Commands blocked with a suggested replacement
+    pub suggested: usize,
+    /// Suggestions followed by another command in the same session
+    pub followed_up: usize,
+    /// Suggestions whose next command was the suggested replacement
+    pub adopted: usize,
+}
+
+impl RuleAdoption {
+    /// Returns the share of followed-up suggestions that were adopted.
+    pub fn rate(&self) -> Option<f64> {
+        (self.followed_up > 0).then(|| self.adopted as f64 / self.followed_up as f64)
+    }
+}
+
+/// Computes per-rule adoption from chronologically ordered records.
+///
+/// A suggestion ("mapped" record) is adopted when the next command in the
+/// same session is exactly the suggested replacement. Rules are returned in
+/// order of hits.
+pub fn rule_adoption(records: &[CommandRecord]) -> Vec<RuleAdoption> {
+    let mut adoption: Vec<RuleAdoption> = Vec::new();
+
+    for (index, record) in records.iter().enumerate() {
+        let Some(rule_id) = &record.rule_id else {
+            continue;
+        };
+
+        let entry = match adoption.iter().position(|entry| &entry.rule_id == rule_id) {
+            Some(position) => &mut adoption[position],
+            None => {
+                adoption.push(RuleAdoption {
+                    rule_id: rule_id.clone(),
+                    hits: 0,
+                    suggested: 0,
+                    followed_up: 0,
+                    adopted: 0,
+                });
+                adoption.last_mut().expect("entry was just pushed")
+            }
+        };
+        entry.hits += 1;
+
+        let Some(suggestion) = record.suggested_command.as_deref().filter(|_| record.status == "mapped") else {
+            continue;
+        };
+        entry.suggested += 1;
+
+        let next = records[index + 1..]
+            .iter()
+            .find(|next| next.session_id == record.session_id);
+        if let Some(next) = next {
+            entry.followed_up += 1;
+            if next.command.trim() == suggestion.trim() {
+                entry.adopted += 1;
+            }
+        }
+    }
+
+    adoption.sort_by(|a, b| b.hits.cmp(&a.hits).then(a.rule_id.cmp(&b.rule_id)));
+    adoption
+}
+
 /// Update the status of the most recent pending command matching criteria
 pub fn update_command_status(
     conn: &Connection,
@@ -346,4 +429,89 @@ mod tests {
         assert!(results[0].was_replaced);
         assert_eq!(results[0].original_command, Some("npm install".to_string()));
     }
+
+    #[test]
+    fn test_rule_tracking_columns() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let db_path = temp_file.path().to_path_buf();
+        let conn = init_database(&db_path).unwrap();
+
+        let record = CommandRecord {
+            rule_id: Some("npm".to_string()),
+            suggested_command: Some("bun install".to_string()),
+            ..create_record("test-session", "npm install", None, None, false, None, "mapped")
+        };
+        log_command(&conn, &record).unwrap();
+
+        // Mapped commands never ran, so they are not failures
+        let failures = query_history(&conn, &HistoryQuery { failures_only: true, ..Default::default() }).unwrap();
+        assert!(failures.is_empty());
+
+        let results = query_history(&conn, &HistoryQuery::default()).unwrap();
+        assert_eq!(results[0].status, "mapped");
+        assert_eq!(results[0].rule_id.as_deref(), Some("npm"));
+        assert_eq!(results[0].suggested_command.as_deref(), Some("bun install"));
+    }
+
+    #[test]
+    fn test_migration_adds_rule_columns() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let db_path = temp_file.path().to_path_buf();
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute(
+            "CREATE TABLE commands (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp TEXT NOT NULL,
+                session_id TEXT NOT NULL,
+                command TEXT NOT NULL,
+                exit_code INTEGER,
+                cwd TEXT,
+                was_replaced INTEGER NOT NULL DEFAULT 0,
+                original_command TEXT
+            )",
+            [],
+        )
+        .unwrap();
+        drop(conn);
+
+        let conn = init_database(&db_path).unwrap();
+        log_command(&conn, &create_record("s1", "ls", Some(0), None, false, None, "success")).unwrap();
+        let results = query_history(&conn, &HistoryQuery::default()).unwrap();
+        assert_eq!(results[0].rule_id, None);
+    }
+
+    #[test]
+    fn test_rule_adoption() {
+        let mapped = |session: &str, command: &str, rule: &str, suggestion: &str| CommandRecord {
+            rule_id: Some(rule.to_string()),
+            suggested_command: Some(suggestion.to_string()),
+            ..create_record(session, command, None, None, false, None, "mapped")
+        };
+        let ran = |session: &str, command: &str| create_record(session, command, Some(0), None, false, None, "success");
+
+        let records = vec![
+            mapped("s1", "npm install", "npm", "bun install"),
+            mapped("s2", "npm test", "npm", "bun test"),
+            ran("s1", "bun install"),
+            ran("s2", "npm run test"),
+            mapped("s1", "grep -r foo", "grep", "rg foo"),
+            ran("s1", "rg foo"),
+            // Last command of its session: no follow-up yet
+            mapped("s3", "npm ci", "npm", "bun install --frozen-lockfile"),
+            CommandRecord {
+                rule_id: Some("git_reset_hard".to_string()),
+                ..create_record("s1", "git reset --hard", None, None, false, None, "denied")
+            },
+        ];
+
+        let adoption = rule_adoption(&records);
+        assert_eq!(
+            adoption[0],
+            RuleAdoption { rule_id: "npm".to_string(), hits: 3, suggested: 3, followed_up: 2, adopted: 1 }
+        );
+        assert_eq!(adoption[0].rate(), Some(0.5));
+        assert_eq!(adoption[1].rule_id, "git_reset_hard");
+        assert_eq!(adoption[1].rate(), None);
+        assert_eq!(adoption[2].rate(), Some(1.0));
+    }
 }
@@ -596,13 +596,13 @@ mod tests {
         #[cfg(debug_assertions)]
         {
             // We're in a debug build - this should be true
-            assert!(cfg!(debug_assertions));
+            const { assert!(cfg!(debug_assertions)) };
         }
         
         #[cfg(not(debug_assertions))]
         {
             // We're in a release build - this should be false
-            assert!(!cfg!(debug_assertions));
+            const { assert!(!cfg!(debug_assertions)) };
         }
     }
 
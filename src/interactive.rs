@@ -0,0 +1,632 @@
+//! Detector for interactive and long-running Bash commands
+//!
+//! Claude's Bash tool has no terminal: editors, pagers, REPLs and prompts wait
+//! for input that never arrives, and dev servers never exit. Detectors run
+//! against every simple command in a command line (see `shell::parse`). Each
+//! built-in detector has a default action that can be changed through
+//! `[interactive_command_overrides]`, and `[[interactive_commands]]` adds
+//! project-specific commands.
+
+use crate::rules::literal_regex;
+use crate::shell::{self, SimpleCommand};
+use crate::types::{Config, InteractiveAction};
+use anyhow::Result;
+use std::ops::Range;
+
+/// Editors that take over the terminal
+const EDITORS: &[&str] = &["vi", "vim", "nvim", "nano", "emacs", "micro", "pico", "joe", "hx"];
+
+/// Pagers that wait for keypresses
+const PAGERS: &[&str] = &["less", "more", "most"];
+
+/// Interpreters that start a REPL when given no script
+const INTERPRETERS: &[&str] = &["python", "python2", "python3", "ipython", "node", "irb", "ghci", "lua"];
+
+/// Package managers whose `init` prompts for answers
+const INIT_PROMPTS: &[&str] = &["npm", "pnpm", "yarn", "bun"];
+
+/// System package managers that ask for confirmation
+const SYSTEM_PACKAGE_MANAGERS: &[&str] = &["apt", "apt-get", "dnf", "yum"];
+
+/// Package script names that usually start a server or watcher
+const LONG_RUNNING_SCRIPTS: &[&str] = &["dev", "start", "serve", "watch"];
+
+/// A simple command being checked, with the context detectors need.
+pub struct Invocation<'a> {
+    /// The full command line
+    pub line: &'a str,
+    pub command: &'a SimpleCommand,
+    /// Unquoted words from the command word onwards
+    pub argv: Vec<&'a str>,
+}
+
+/// A built-in detector for commands that need a terminal or never exit.
+pub struct InteractiveDetector {
+    pub rule_name: &'static str,
+    /// Explanation shown when the command is blocked
+    pub reason: &'static str,
+    pub default_action: InteractiveAction,
+    /// Whether the command runs until stopped (never flagged when backgrounded)
+    pub long_running: bool,
+    detect: fn(&Invocation) -> bool,
+    /// Non-interactive form of the simple command, if there is one
+    rewrite: fn(&Invocation) -> Option<String>,
+}
+
+/// A command caught by an interactive command detector.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InteractiveMatch {
+    pub rule_name: String,
+    pub reason: String,
+    /// Effective action; `rewrite` only when a non-interactive form exists
+    pub action: InteractiveAction,
+    /// The simple command that matched, from its command word
+    pub command: String,
+    /// Full command line with every rewritable command rewritten
+    pub replacement: Option<String>,
+}
+
+/// Returns all built-in interactive command detectors.
+pub fn get_default_interactive_detectors() -> Vec<InteractiveDetector> {
+    vec![
+        InteractiveDetector {
+            rule_name: "editor",
+            reason: "Editors need a terminal, which the Bash tool does not have. Use the Edit or Write tool to change files.",
+            default_action: InteractiveAction::Deny,
+            long_running: false,
+            detect: detect_editor,
+            rewrite: no_rewrite,
+        },
+        InteractiveDetector {
+            rule_name: "pager",
+            reason: "Pagers wait for keypresses. Use cat, head or tail to read files.",
+            default_action: InteractiveAction::Rewrite,
+            long_running: false,
+            detect: detect_pager,
+            rewrite: rewrite_pager,
+        },
+        InteractiveDetector {
+            rule_name: "git_pager",
+            reason: "git output is piped through a pager. Pass --no-pager to print it directly.",
+            // git only pages on a terminal, so this is opt-in
+            default_action: InteractiveAction::Off,
+            long_running: false,
+            detect: detect_git_pager,
+            rewrite: rewrite_git_pager,
+        },
+        InteractiveDetector {
+            rule_name: "git_interactive",
+            reason: "Interactive git commands wait for input. Stage paths explicitly, or set GIT_SEQUENCE_EDITOR=true for a non-interactive rebase.",
+            default_action: InteractiveAction::Deny,
+            long_running: false,
+            detect: detect_git_interactive,
+            rewrite: rewrite_git_interactive,
+        },
+        InteractiveDetector {
+            rule_name: "git_editor",
+            reason: "This git command opens an editor for the commit message. Pass the message with -m, or keep the default message with --no-edit.",
+            default_action: InteractiveAction::Rewrite,
+            long_running: false,
+            detect: detect_git_editor,
+            rewrite: rewrite_git_editor,
+        },
+        InteractiveDetector {
+            rule_name: "init_prompt",
+            reason: "Project initializers prompt for answers. Accept the defaults with -y.",
+            default_action: InteractiveAction::Rewrite,
+            long_running: false,
+            detect: detect_init_prompt,
+            rewrite: rewrite_init_prompt,
+        },
+        InteractiveDetector {
+            rule_name: "package_confirmation",
+            reason: "The package manager asks for confirmation. Answer it up front with -y.",
+            default_action: InteractiveAction::Rewrite,
+            long_running: false,
+            detect: detect_package_confirmation,
+            rewrite: rewrite_package_confirmation,
+        },
+        InteractiveDetector {
+            rule_name: "repl",
+            reason: "Without a script this starts an interactive REPL. Pass code with -c/-e, a script file, or a heredoc.",
+            default_action: InteractiveAction::Deny,
+            long_running: false,
+            detect: detect_repl,
+            rewrite: no_rewrite,
+        },
+        InteractiveDetector {
+            rule_name: "long_running",
+            reason: "This command runs until it is stopped (a server or watcher) and would block the Bash tool. Run it with run_in_background: true and check its output later.",
+            default_action: InteractiveAction::Background,
+            long_running: true,
+            detect: detect_long_running,
+            rewrite: no_rewrite,
+        },
+    ]
+}
+
+/// Checks a command line for commands that need a terminal or never exit.
+///
+/// Project-specific `[[interactive_commands]]` are checked before the
+/// built-in detectors. If any matched command is denied, that match is
+/// returned; otherwise all rewritable commands are rewritten together.
+///
+/// # Arguments
+/// * `config` - Configuration containing overrides and custom commands
+/// * `command` - The bash command line to check
+/// * `background` - Whether the Bash tool runs the command in the background
+///
+/// # Returns
+/// * `Ok(Some(match))` - A command needs a terminal or never exits
+/// * `Ok(None)` - The command line can run as-is
+/// * `Err` - If a custom pattern cannot be compiled
+pub fn check_interactive_command(config: &Config, command: &str, background: bool) -> Result<Option<InteractiveMatch>> {
+    let detectors: Vec<(InteractiveDetector, InteractiveAction)> = get_default_interactive_detectors()
+        .into_iter()
+        .map(|detector| {
+            let action = config
+                .interactive_command_overrides
+                .get(detector.rule_name)
+                .copied()
+                .unwrap_or(detector.default_action);
+            (detector, action)
+        })
+        .filter(|(_, action)| *action != InteractiveAction::Off)
+        .collect();
+
+    let mut first_rewrite: Option<InteractiveMatch> = None;
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+
+    for simple_command in shell::parse(command) {
+        let invocation = Invocation { line: command, command: &simple_command, argv: simple_command.argv() };
+        if invocation.argv.is_empty() {
+            continue;
+        }
+        let backgrounded = background || is_backgrounded(&invocation);
+
+        let Some((rule_name, reason, action, rewritten)) =
+            match_custom(config, &invocation, backgrounded)?.or_else(|| match_builtin(&detectors, &invocation, backgrounded))
+        else {
+            continue;
+        };
+
+        let found = InteractiveMatch {
+            rule_name,
+            reason,
+            action,
+            command: simple_command.command_text(command).to_string(),
+            replacement: None,
+        };
+
+        match rewritten {
+            Some(rewritten) if action == InteractiveAction::Rewrite => {
+                edits.push((simple_command.command_range(), rewritten));
+                first_rewrite.get_or_insert(found);
+            }
+            // No non-interactive form: a rewrite falls back to deny
+            _ if action == InteractiveAction::Rewrite => {
+                return Ok(Some(InteractiveMatch { action: InteractiveAction::Deny, ..found }));
+            }
+            _ => return Ok(Some(found)),
+        }
+    }
+
+    Ok(first_rewrite.map(|found| InteractiveMatch {
+        replacement: Some(shell::splice(command, &edits)),
+        ..found
+    }))
+}
+
+/// A detector hit: rule name, reason, effective action and the rewritten simple command.
+type Hit = (String, String, InteractiveAction, Option<String>);
+
+/// Matches the project-specific `[[interactive_commands]]`.
+fn match_custom(config: &Config, invocation: &Invocation, backgrounded: bool) -> Result<Option<Hit>> {
+    let text = invocation.command.command_text(invocation.line);
+
+    for custom in &config.interactive_commands {
+        let long_running = custom.action == InteractiveAction::Background;
+        if custom.action == InteractiveAction::Off || (long_running && backgrounded) {
+            continue;
+        }
+
+        if !literal_regex(&custom.pattern)?.is_match(text) {
+            continue;
+        }
+
+        let reason = custom
+            .reason
+            .clone()
+            .unwrap_or_else(|| "This command needs an interactive terminal, which the Bash tool does not have.".to_string());
+        let rewritten = custom
+            .replacement
+            .as_ref()
+            .map(|replacement| format!("{replacement}{}", &text[custom.pattern.len()..]));
+        return Ok(Some((custom.id().to_string(), reason, custom.action, rewritten)));
+    }
+
+    Ok(None)
+}
+
+/// Matches the enabled built-in detectors.
+fn match_builtin(
+    detectors: &[(InteractiveDetector, InteractiveAction)],
+    invocation: &Invocation,
+    backgrounded: bool,
+) -> Option<Hit> {
+    detectors
+        .iter()
+        .filter(|(detector, _)| !(detector.long_running && backgrounded))
+        .find(|(detector, _)| (detector.detect)(invocation))
+        .map(|(detector, action)| {
+            (
+                detector.rule_name.to_string(),
+                detector.reason.to_string(),
+                *action,
+                (detector.rewrite)(invocation),
+            )
+        })
+}
+
+/// Returns true if the simple command is followed by `&`.
+fn is_backgrounded(invocation: &Invocation) -> bool {
+    let rest = invocation.line[invocation.command.end..].trim_start();
+    rest.starts_with('&') && !rest.starts_with("&&")
+}
+
+/// Returns true if the command reads stdin from a pipe, file or heredoc.
+fn has_stdin(invocation: &Invocation) -> bool {
+    let before = invocation.line[..invocation.command.start].trim_end();
+    let piped = before.ends_with('|') && !before.ends_with("||");
+    piped
+        || invocation.argv.iter().any(|word| {
+            shell::is_redirection(word) && word.trim_start_matches(|c: char| c.is_ascii_digit()).starts_with('<')
+        })
+}
+
+/// Returns the arguments after the command word, without redirections.
+fn plain_args<'a>(invocation: &Invocation<'a>) -> Vec<&'a str> {
+    let mut args = Vec::new();
+    let mut skip_target = false;
+    for word in invocation.argv.iter().skip(1) {
+        if skip_target {
+            skip_target = false;
+        } else if shell::is_redirection(word) {
+            skip_target = shell::redirection_needs_target(word);
+        } else {
+            args.push(*word);
+        }
+    }
+    args
+}
+
+/// Returns true if any argument is one of `flags`. Long flags also match their
+/// `--flag=value` form and single-letter flags match when combined (`-am`).
+fn has_any(args: &[&str], flags: &[&str]) -> bool {
+    args.iter().any(|arg| {
+        flags.iter().any(|flag| {
+            arg == flag
+                || (flag.starts_with("--") && arg.strip_prefix(flag).is_some_and(|rest| rest.starts_with('=')))
+                || (flag.len() == 2
+                    && arg.strip_prefix('-').is_some_and(|letters| is_flag_cluster(letters) && letters.contains(&flag[1..])))
+        })
+    })
+}
+
+/// Returns true if `letters` looks like combined single-letter flags (`am`)
+/// rather than a flag with an attached value (`Wdefault`, `Xdev`): distinct
+/// ASCII letters, with no uppercase letter followed by lowercase ones.
+fn is_flag_cluster(letters: &str) -> bool {
+    let bytes = letters.as_bytes();
+    bytes.iter().all(u8::is_ascii_alphabetic)
+        && bytes.iter().enumerate().all(|(i, byte)| !bytes[..i].contains(byte))
+        && !bytes.windows(2).any(|pair| pair[0].is_ascii_uppercase() && pair[1].is_ascii_lowercase())
+}
+
+/// Returns the program and its plain arguments.
+fn split_program<'a>(invocation: &Invocation<'a>) -> (&'a str, Vec<&'a str>) {
+    (invocation.argv[0], plain_args(invocation))
+}
+
+/// Returns the git subcommand and its arguments, skipping global options.
+fn git_subcommand<'a>(invocation: &Invocation<'a>) -> Option<(&'a str, Vec<&'a str>)> {
+    let (program, args) = split_program(invocation);
+    if program != "git" {
+        return None;
+    }
+
+    shell::git_subcommand(&args).map(|(subcommand, rest)| (subcommand, rest.to_vec()))
+}
+
+/// Text of the simple command after its command word, with its original quoting.
+fn raw_rest<'a>(invocation: &Invocation<'a>) -> &'a str {
+    let command = invocation.command;
+    let program_end = command.words[command.command_index].end;
+    &invocation.line[program_end..command.end]
+}
+
+/// Text of the simple command from its command word, with its original quoting.
+fn raw_text<'a>(invocation: &Invocation<'a>) -> &'a str {
+    invocation.command.command_text(invocation.line)
+}
+
+fn no_rewrite(_: &Invocation) -> Option<String> {
+    None
+}
+
+fn detect_editor(invocation: &Invocation) -> bool {
+    let (program, args) = split_program(invocation);
+    EDITORS.contains(&program) && !has_any(&args, &["--version", "--help", "--batch", "-es", "-Es"])
+}
+
+fn detect_pager(invocation: &Invocation) -> bool {
+    PAGERS.contains(&split_program(invocation).0)
+}
+
+fn rewrite_pager(invocation: &Invocation) -> Option<String> {
+    // Pager options (-R, +F, ...) mean nothing to cat, so only files are kept
+    let command = invocation.command;
+    let files: Vec<&str> = command.words[command.command_index + 1..]
+        .iter()
+        .filter(|word| !word.value.starts_with('-') && !word.value.starts_with('+'))
+        .map(|word| &invocation.line[word.start..word.end])
+        .collect();
+
+    Some(std::iter::once("cat").chain(files).collect::<Vec<_>>().join(" "))
+}
+
+fn detect_git_pager(invocation: &Invocation) -> bool {
+    let paging = matches!(
+        git_subcommand(invocation),
+        Some(("log" | "show" | "diff" | "blame" | "shortlog" | "reflog" | "grep", _))
+    );
+    paging && !invocation.argv.contains(&"--no-pager")
+}
+
+fn rewrite_git_pager(invocation: &Invocation) -> Option<String> {
+    Some(format!("git --no-pager{}", raw_rest(invocation)))
+}
+
+fn detect_git_interactive(invocation: &Invocation) -> bool {
+    let Some((subcommand, args)) = git_subcommand(invocation) else {
+        return false;
+    };
+
+    match subcommand {
+        "rebase" => has_any(&args, &["-i", "--interactive"]),
+        "add" => has_any(&args, &["-i", "--interactive", "-p", "--patch", "-e", "--edit"]),
+        "checkout" | "reset" | "restore" | "stash" | "commit" => has_any(&args, &["-p", "--patch"]),
+        "clean" => has_any(&args, &["-i", "--interactive"]),
+        "mergetool" => true,
+        _ => false,
+    }
+}
+
+fn rewrite_git_interactive(invocation: &Invocation) -> Option<String> {
+    // An interactive rebase with the default todo list (e.g. for --autosquash)
+    let (subcommand, _) = git_subcommand(invocation)?;
+    (subcommand == "rebase").then(|| format!("GIT_SEQUENCE_EDITOR=true GIT_EDITOR=true {}", raw_text(invocation)))
+}
+
+fn detect_git_editor(invocation: &Invocation) -> bool {
+    match git_subcommand(invocation) {
+        Some(("commit", args)) => !has_any(
+            &args,
+            &["-m", "--message", "-F", "--file", "-C", "--reuse-message", "--no-edit", "--fixup", "--dry-run", "-p", "--patch"],
+        ),
+        Some(("tag", args)) => {
+            has_any(&args, &["-a", "--annotate", "-s", "--sign"]) && !has_any(&args, &["-m", "--message", "-F", "--file"])
+        }
+        Some(("merge", args)) => has_any(&args, &["-e", "--edit"]),
+        Some(("revert", args)) => !has_any(&args, &["--no-edit", "-n", "--no-commit", "--abort", "--quit", "--skip"]),
+        _ => false,
+    }
+}
+
+fn rewrite_git_editor(invocation: &Invocation) -> Option<String> {
+    // GIT_EDITOR=true keeps the default message; a new commit has none
+    match git_subcommand(invocation)? {
+        ("commit", args) if !has_any(&args, &["--amend"]) => None,
+        ("tag", _) => None,
+        _ => Some(format!("GIT_EDITOR=true {}", raw_text(invocation))),
+    }
+}
+
+fn detect_init_prompt(invocation: &Invocation) -> bool {
+    let (program, args) = split_program(invocation);
+    INIT_PROMPTS.contains(&program) && args.first() == Some(&"init") && !has_any(&args, &["-y", "--yes"])
+}
+
+fn rewrite_init_prompt(invocation: &Invocation) -> Option<String> {
+    Some(format!("{} -y", raw_text(invocation)))
+}
+
+fn detect_package_confirmation(invocation: &Invocation) -> bool {
+    let (program, args) = split_program(invocation);
+    SYSTEM_PACKAGE_MANAGERS.contains(&program)
+        && args.iter().find(|arg| !arg.starts_with('-')).is_some_and(|subcommand| {
+            matches!(*subcommand, "install" | "remove" | "purge" | "upgrade" | "dist-upgrade" | "full-upgrade" | "autoremove" | "erase" | "update")
+                && !(*subcommand == "update" && program.starts_with("apt"))
+        })
+        && !has_any(&args, &["-y", "--yes", "--assume-yes", "-qq"])
+}
+
+fn rewrite_package_confirmation(invocation: &Invocation) -> Option<String> {
+    Some(format!("{} -y{}", invocation.argv[0], raw_rest(invocation)))
+}
+
+fn detect_repl(invocation: &Invocation) -> bool {
+    let (program, args) = split_program(invocation);
+    if !INTERPRETERS.contains(&program) || has_stdin(invocation) {
+        return false;
+    }
+
+    // Any script, code or informational flag means no REPL
+    let runs_code = has_any(
+        &args,
+        &["-c", "-m", "-e", "--eval", "-p", "--print", "-r", "--version", "-V", "--help", "-h"],
+    );
+    let has_script = args.iter().any(|arg| !arg.starts_with('-'));
+    !runs_code && !has_script
+}
+
+fn detect_long_running(invocation: &Invocation) -> bool {
+    let (program, args) = split_program(invocation);
+    let arg = |index: usize| args.get(index).copied().unwrap_or_default();
+
+    match program {
+        "npm" | "pnpm" | "yarn" | "bun" => {
+            let script = if arg(0) == "run" { arg(1) } else { arg(0) };
+            LONG_RUNNING_SCRIPTS.contains(&script)
+        }
+        "vite" => args.is_empty() || matches!(arg(0), "dev" | "serve" | "preview"),
+        "next" | "nuxt" | "astro" => matches!(arg(0), "dev" | "start"),
+        "webpack" => arg(0) == "serve",
+        "webpack-dev-server" | "nodemon" | "uvicorn" | "gunicorn" | "watch" => true,
+        "rails" | "bin/rails" => matches!(arg(0), "server" | "s"),
+        "flask" => arg(0) == "run",
+        "hugo" => arg(0) == "server",
+        "jekyll" | "mkdocs" => arg(0) == "serve",
+        "php" => args.contains(&"-S"),
+        "cargo" => arg(0) == "watch",
+        "tail" => has_any(&args, &["-f", "-F", "--follow"]),
+        "tsc" | "jest" | "vitest" if has_any(&args, &["--watch", "-w"]) => true,
+        "docker" => arg(0) == "compose" && arg(1) == "up" && !has_any(&args, &["-d", "--detach"]),
+        "docker-compose" => arg(0) == "up" && !has_any(&args, &["-d", "--detach"]),
+        "kubectl" => arg(0) == "port-forward" || (arg(0) == "logs" && has_any(&args, &["-f", "--follow"])),
+        _ if program.starts_with("python") => {
+            (arg(0) == "-m" && arg(1) == "http.server") || args.contains(&"runserver")
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(command: &str) -> Option<(String, &'static str, Option<String>)> {
+        check_interactive_command(&Config::default(), command, false)
+            .unwrap()
+            .map(|found| (found.rule_name, found.action.as_str(), found.replacement))
+    }
+
+    #[test]
+    fn test_denies_interactive_commands() {
+        let cases = [
+            ("vim src/main.rs", "editor"),
+            ("sudo nano /etc/hosts", "editor"),
+            ("git rebase -i HEAD~3", "git_interactive"),
+            ("git add -p", "git_interactive"),
+            ("git commit", "git_editor"),
+            ("git commit -a", "git_editor"),
+            ("git tag -a v1.0", "git_editor"),
+            ("python3", "repl"),
+            ("cd app && node", "repl"),
+            ("python3 -Wdefault", "repl"),
+            ("python3 -Xdev", "repl"),
+            ("git commit -av", "git_editor"),
+        ];
+
+        for (command, rule_name) in cases {
+            assert_eq!(check(command), Some((rule_name.to_string(), "deny", None)), "{command}");
+        }
+    }
+
+    #[test]
+    fn test_rewrites_to_non_interactive_forms() {
+        let cases = [
+            ("less -R README.md", "pager", "cat README.md"),
+            ("git commit --amend", "git_editor", "GIT_EDITOR=true git commit --amend"),
+            ("git revert HEAD", "git_editor", "GIT_EDITOR=true git revert HEAD"),
+            ("cd web && npm init", "init_prompt", "cd web && npm init -y"),
+            ("sudo apt-get install ripgrep", "package_confirmation", "sudo apt-get -y install ripgrep"),
+            ("npm init && less package.json", "init_prompt", "npm init -y && cat package.json"),
+            ("cat README.md | less", "pager", "cat README.md | cat"),
+        ];
+
+        for (command, rule_name, replacement) in cases {
+            assert_eq!(
+                check(command),
+                Some((rule_name.to_string(), "rewrite", Some(replacement.to_string()))),
+                "{command}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_long_running_commands_suggest_background() {
+        for command in ["npm run dev", "bun dev", "python3 -m http.server 8000", "tail -f log/dev.log", "docker compose up"] {
+            assert_eq!(check(command).map(|(rule, action, _)| (rule, action)), Some(("long_running".to_string(), "background")), "{command}");
+        }
+
+        // Already in the background
+        assert_eq!(check("npm run dev &"), None);
+        assert_eq!(check("npm run dev & sleep 5"), None);
+        assert!(check_interactive_command(&Config::default(), "npm run dev", true).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_allows_non_interactive_commands() {
+        let cases = [
+            "git commit -m 'fix: typo'",
+            "git commit -am 'fix: typo'",
+            "git commit --amend --no-edit",
+            "git rebase main",
+            "git log --oneline",
+            "python3 script.py",
+            "python -c 'print(1)'",
+            "echo 'print(1)' | python3",
+            "python3 <<'EOF'\nprint(1)\nEOF",
+            "node --version",
+            "npm init -y",
+            "npm run build",
+            "apt-get install -y ripgrep",
+            "vim --version",
+            "docker compose up -d",
+        ];
+
+        for command in cases {
+            assert_eq!(check(command), None, "{command}");
+        }
+    }
+
+    #[test]
+    fn test_overrides_and_custom_commands() {
+        let config: Config = toml::from_str(r#"
+[interactive_command_overrides]
+editor = "off"
+git_pager = "rewrite"
+pager = "deny"
+
+[[interactive_commands]]
+pattern = "rails console"
+reason = "Use rails runner instead."
+replacement = "rails runner"
+action = "rewrite"
+
+[[interactive_commands]]
+id = "storybook"
+pattern = "npm run storybook"
+action = "background"
+"#).unwrap();
+        let check = |command: &str| {
+            check_interactive_command(&config, command, false)
+                .unwrap()
+                .map(|found| (found.rule_name, found.action.as_str(), found.replacement))
+        };
+
+        assert_eq!(check("vim notes.txt"), None);
+        assert_eq!(check("less notes.txt"), Some(("pager".to_string(), "deny", None)));
+        assert_eq!(
+            check("git log -5"),
+            Some(("git_pager".to_string(), "rewrite", Some("git --no-pager log -5".to_string())))
+        );
+        assert_eq!(
+            check("rails console 'p User.count'"),
+            Some(("rails console".to_string(), "rewrite", Some("rails runner 'p User.count'".to_string())))
+        );
+        assert_eq!(check("npm run storybook"), Some(("storybook".to_string(), "background", None)));
+        assert_eq!(check("npm run storybook &"), None);
+    }
+}
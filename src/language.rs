@@ -0,0 +1,166 @@
+//! Language detection for edited files
+//!
+//! Security patterns can be scoped to languages (see `SecurityPattern`), so
+//! `eval(` in a Python file gets the Python reminder rather than the
+//! JavaScript one. The language comes from the file extension or name, and
+//! for scripts without either from the shebang line.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// File extensions (lowercase, without the dot) and the language they contain
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("js", "javascript"),
+    ("mjs", "javascript"),
+    ("cjs", "javascript"),
+    ("jsx", "javascript"),
+    ("ts", "typescript"),
+    ("mts", "typescript"),
+    ("cts", "typescript"),
+    ("tsx", "typescript"),
+    ("html", "html"),
+    ("htm", "html"),
+    ("vue", "html"),
+    ("svelte", "html"),
+    ("py", "python"),
+    ("pyw", "python"),
+    ("pyi", "python"),
+    ("rb", "ruby"),
+    ("rake", "ruby"),
+    ("gemspec", "ruby"),
+    ("php", "php"),
+    ("phtml", "php"),
+    ("rs", "rust"),
+    ("go", "go"),
+    ("swift", "swift"),
+    ("java", "java"),
+    ("sh", "shell"),
+    ("bash", "shell"),
+    ("zsh", "shell"),
+];
+
+/// File names without a telling extension
+const FILE_NAMES: &[(&str, &str)] = &[
+    ("Rakefile", "ruby"),
+    ("Gemfile", "ruby"),
+    ("Podfile", "ruby"),
+    ("Vagrantfile", "ruby"),
+];
+
+/// Shebang interpreters (with version suffixes stripped) and their language
+const INTERPRETERS: &[(&str, &str)] = &[
+    ("node", "javascript"),
+    ("deno", "typescript"),
+    ("bun", "javascript"),
+    ("python", "python"),
+    ("ruby", "ruby"),
+    ("php", "php"),
+    ("sh", "shell"),
+    ("bash", "shell"),
+    ("zsh", "shell"),
+    ("dash", "shell"),
+];
+
+/// Detects the language of a file.
+///
+/// The extension or file name decides when it is known. Otherwise the
+/// shebang is read from the new content, or from the file on disk when the
+/// content does not start with one (an `Edit` usually replaces a snippet in
+/// the middle of the file).
+///
+/// # Returns
+/// * `Some(language)` - e.g. "python", "javascript", "shell"
+/// * `None` - If the language cannot be told
+pub fn detect_language(file_path: &str, content: &str) -> Option<&'static str> {
+    let path = Path::new(file_path);
+
+    if let Some(extension) = file_extension(file_path) {
+        if let Some((_, language)) = EXTENSIONS.iter().find(|(known, _)| *known == extension) {
+            return Some(language);
+        }
+    }
+
+    if let Some(name) = path.file_name().and_then(|name| name.to_str()) {
+        if let Some((_, language)) = FILE_NAMES.iter().find(|(known, _)| *known == name) {
+            return Some(language);
+        }
+    }
+
+    let first_line = match content.lines().next() {
+        Some(line) if line.starts_with("#!") => line.to_string(),
+        _ => read_first_line(path)?,
+    };
+    shebang_language(&first_line)
+}
+
+/// Returns the lowercase extension of a file path, without the dot.
+pub fn file_extension(file_path: &str) -> Option<String> {
+    Path::new(file_path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase)
+}
+
+/// Maps a `#!` line to a language, e.g. `#!/usr/bin/env python3` → python.
+fn shebang_language(line: &str) -> Option<&'static str> {
+    let mut words = line.strip_prefix("#!")?.split_whitespace();
+    let mut interpreter = basename(words.next()?);
+
+    // `#!/usr/bin/env -S python3 -u`, `#!/usr/bin/env PYTHONUTF8=1 python3`
+    if interpreter == "env" {
+        interpreter = basename(words.find(|word| !word.starts_with('-') && !word.contains('='))?);
+    }
+
+    let name = interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    INTERPRETERS
+        .iter()
+        .find(|(known, _)| *known == name)
+        .map(|(_, language)| *language)
+}
+
+fn basename(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Reads the first line of a file, if it exists and is readable.
+fn read_first_line(path: &Path) -> Option<String> {
+    let file = File::open(path).ok()?;
+    let mut line = String::new();
+    BufReader::new(file).read_line(&mut line).ok()?;
+    Some(line.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_language_from_extension_and_name() {
+        assert_eq!(detect_language("src/app.JS", ""), Some("javascript"));
+        assert_eq!(detect_language("src/App.tsx", ""), Some("typescript"));
+        assert_eq!(detect_language("app/models/user.rb", ""), Some("ruby"));
+        assert_eq!(detect_language("Rakefile", ""), Some("ruby"));
+        assert_eq!(detect_language("scripts/build.py", "#!/bin/bash"), Some("python"));
+        assert_eq!(detect_language("README.md", ""), None);
+    }
+
+    #[test]
+    fn test_detects_language_from_shebang() {
+        assert_eq!(detect_language("bin/deploy", "#!/usr/bin/env python3\nimport os"), Some("python"));
+        assert_eq!(detect_language("bin/deploy", "#!/usr/bin/env -S python3.11 -u"), Some("python"));
+        assert_eq!(detect_language("bin/serve", "#!/usr/bin/env node"), Some("javascript"));
+        assert_eq!(detect_language("bin/setup", "#!/bin/bash\nset -e"), Some("shell"));
+        assert_eq!(detect_language("bin/tool", "#!/usr/bin/perl"), None);
+        assert_eq!(detect_language("/nonexistent/bin/tool", "print(1)"), None);
+    }
+
+    #[test]
+    fn test_reads_shebang_from_file_on_disk() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let script = temp_dir.path().join("release");
+        std::fs::write(&script, "#!/usr/bin/env ruby\nputs 1\n").unwrap();
+
+        assert_eq!(detect_language(script.to_str().unwrap(), "eval(code)"), Some("ruby"));
+    }
+}
@@ -17,5 +17,18 @@ mod config;
 mod hooks;
 mod installer;
 mod directory;
+mod doctor;
+mod env_rules;
+mod interactive;
+mod language;
+mod explain;
+mod lint;
+mod policy;
+mod rule_tests;
+mod rules;
+mod secrets;
+mod shell;
+mod suggest;
+mod translate;
 pub mod history;
 pub mod security;
\ No newline at end of file
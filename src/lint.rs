@@ -0,0 +1,164 @@
+//! Command rule linting (`lint`)
+
+use crate::config::load_layered_config;
+use crate::rules::{find_cycles, find_shadowed, ordered_rules, Rule};
+use crate::types::Config;
+use anyhow::Result;
+use serde::Serialize;
+
+/// Shell builtins that never appear on PATH
+const SHELL_BUILTINS: &[&str] = &["cd", "export", "source", ".", "exit", "alias", "unset", "set", "eval"];
+
+/// A problem found in the command rules
+#[derive(Debug, Serialize, PartialEq)]
+pub struct LintIssue {
+    /// Issue type: "cycle", "shadowed" or "missing_binary"
+    pub kind: &'static str,
+    pub message: String,
+}
+
+/// Full lint report, serialized as-is for `--json`
+#[derive(Debug, Serialize)]
+pub struct LintReport {
+    pub ok: bool,
+    pub issues: Vec<LintIssue>,
+}
+
+/// Lints the command rules of the merged configuration and prints the report.
+///
+/// # Arguments
+/// * `config_path` - Project configuration file name
+/// * `json` - Print machine-readable JSON instead of text
+///
+/// # Returns
+/// * `Ok(true)` - No issues were found
+/// * `Ok(false)` - At least one issue was found
+/// * `Err` - If the configuration cannot be loaded
+pub fn run_lint(config_path: &str, json: bool) -> Result<bool> {
+    let config = load_layered_config(config_path, None)?;
+    let issues = lint_config(&config)?;
+    let report = LintReport { ok: issues.is_empty(), issues };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+
+    Ok(report.ok)
+}
+
+/// Collects every lint issue in the configuration.
+fn lint_config(config: &Config) -> Result<Vec<LintIssue>> {
+    let mut issues = Vec::new();
+
+    for cycle in find_cycles(config)? {
+        let mut path = cycle.clone();
+        path.push(cycle[0].clone());
+        issues.push(LintIssue {
+            kind: "cycle",
+            message: format!("Rules form a cycle and are skipped: {}", path.join(" → ")),
+        });
+    }
+
+    for (shadowed, by) in find_shadowed(config)? {
+        issues.push(LintIssue {
+            kind: "shadowed",
+            message: format!("Rule '{shadowed}' never matches because rule '{by}' always matches first"),
+        });
+    }
+
+    let mut checked: Vec<&str> = Vec::new();
+    let rules = ordered_rules(config);
+    for rule in &rules {
+        let Some(binary) = replacement_binary(rule) else {
+            continue;
+        };
+
+        if checked.contains(&binary) || SHELL_BUILTINS.contains(&binary) {
+            continue;
+        }
+        checked.push(binary);
+
+        if which::which(binary).is_err() {
+            issues.push(LintIssue {
+                kind: "missing_binary",
+                message: format!("Rule '{}' suggests '{binary}', which is not on PATH", rule.id()),
+            });
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Returns the program a rule's replacement runs, if it is known statically.
+fn replacement_binary(rule: &Rule) -> Option<&str> {
+    let replacement = match rule {
+        Rule::Command(rule) => &rule.replacement,
+        Rule::Rewrite(rule) => &rule.replace,
+    };
+
+    replacement
+        .split_whitespace()
+        .next()
+        .filter(|binary| !binary.contains('$'))
+}
+
+fn print_report(report: &LintReport) {
+    println!("🔎 Claude Hook Advisor - Rule Lint");
+    println!("==================================");
+
+    for issue in &report.issues {
+        println!("✗ {}: {}", issue.kind, issue.message);
+    }
+
+    if report.ok {
+        println!("✓ No issues found.");
+    } else {
+        println!();
+        println!("{} issue(s) found.", report.issues.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_reports_cycles_shadowing_and_missing_binaries() {
+        let config: Config = toml::from_str(r#"
+[commands]
+wget = "curl -L"
+curl = "wget --verbose"
+"npm test" = "bun test"
+yarn = "no-such-binary-for-tests install"
+
+[[command_rules]]
+pattern = "npm"
+replacement = "bun"
+priority = 5
+"#).unwrap();
+
+        let issues = lint_config(&config).unwrap();
+        let messages = |kind: &str| -> Vec<&str> {
+            issues.iter().filter(|issue| issue.kind == kind).map(|issue| issue.message.as_str()).collect()
+        };
+
+        let cycles = messages("cycle");
+        assert_eq!(cycles.len(), 1);
+        assert!(cycles[0].contains("curl → wget → curl") || cycles[0].contains("wget → curl → wget"));
+        assert_eq!(messages("shadowed"), vec!["Rule 'npm test' never matches because rule 'npm' always matches first"]);
+        assert!(messages("missing_binary").iter().any(|message| message.contains("no-such-binary-for-tests")));
+    }
+
+    #[test]
+    fn test_lint_accepts_idempotent_rules() {
+        let config: Config = toml::from_str(r#"
+[commands]
+curl = "curl -L"
+"#).unwrap();
+
+        let issues = lint_config(&config).unwrap();
+        assert!(issues.iter().all(|issue| issue.kind == "missing_binary"));
+    }
+}
@@ -0,0 +1,334 @@
+//! Built-in policy for destructive Bash commands
+//!
+//! Detectors run against every simple command in a command line (see
+//! `shell::parse`), so wrappers such as `sudo` and compound commands cannot
+//! hide a destructive command. Each detector can be disabled through
+//! `[dangerous_command_overrides]`.
+
+use crate::shell;
+use crate::types::Config;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+/// Branches that must not be force-pushed
+const PROTECTED_BRANCHES: &[&str] = &["main", "master"];
+
+/// `rm` targets that wipe the filesystem root or the home directory
+const RM_CRITICAL_TARGETS: &[&str] = &[
+    "/", "/*", "/.", "~", "~/", "~/*", "~/.", "$HOME", "$HOME/", "$HOME/*", "${HOME}", "${HOME}/", "${HOME}/*",
+];
+
+/// Fork bomb: a function that pipes into itself in the background, e.g. `:(){ :|:& };:`
+static FORK_BOMB: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"([\w:]+)\s*\(\)\s*\{\s*([\w:]+)\s*\|\s*([\w:]+)\s*&\s*\}").expect("valid fork bomb regex")
+});
+
+/// A simple command being checked, with the context detectors need.
+pub struct Invocation<'a> {
+    /// The full command line
+    pub line: &'a str,
+    /// Unquoted words from the command word onwards
+    pub argv: Vec<&'a str>,
+    /// Working directory the command runs in
+    pub cwd: &'a Path,
+}
+
+/// A built-in detector for a destructive command.
+pub struct CommandDetector {
+    pub rule_name: &'static str,
+    /// Explanation shown when the command is blocked
+    pub reason: &'static str,
+    detect: fn(&Invocation) -> bool,
+}
+
+/// A command blocked by the policy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicyViolation {
+    pub rule_name: String,
+    pub reason: String,
+}
+
+/// Returns all built-in destructive command detectors.
+///
+/// These detectors are enabled by default and can be disabled via configuration.
+pub fn get_default_command_detectors() -> Vec<CommandDetector> {
+    vec![
+        CommandDetector {
+            rule_name: "rm_root_or_home",
+            reason: "Recursively deleting the filesystem root or home directory destroys data irrecoverably.",
+            detect: detect_rm_root_or_home,
+        },
+        CommandDetector {
+            rule_name: "git_force_push_protected",
+            reason: "Force-pushing to a protected branch (main/master) rewrites shared history. Push to a feature branch or use a pull request.",
+            detect: detect_git_force_push_protected,
+        },
+        CommandDetector {
+            rule_name: "git_reset_hard",
+            reason: "`git reset --hard` discards uncommitted changes. Use `git stash` to keep them recoverable.",
+            detect: detect_git_reset_hard,
+        },
+        CommandDetector {
+            rule_name: "git_clean_force",
+            reason: "`git clean -f` permanently deletes untracked files (with -x also ignored files such as .env). Preview with `git clean -n` first.",
+            detect: detect_git_clean_force,
+        },
+        CommandDetector {
+            rule_name: "chmod_recursive_777",
+            reason: "`chmod -R 777` makes every file world-writable. Grant only the permissions that are needed.",
+            detect: detect_chmod_recursive_777,
+        },
+        CommandDetector {
+            rule_name: "mkfs",
+            reason: "`mkfs` formats a device and erases everything on it.",
+            detect: detect_mkfs,
+        },
+        CommandDetector {
+            rule_name: "dd_to_device",
+            reason: "`dd of=/dev/...` overwrites a raw device and can destroy a disk.",
+            detect: detect_dd_to_device,
+        },
+        CommandDetector {
+            rule_name: "fork_bomb",
+            reason: "This command is a fork bomb and would exhaust system resources.",
+            detect: detect_fork_bomb,
+        },
+    ]
+}
+
+/// Gets the list of enabled detectors by applying `dangerous_command_overrides`.
+///
+/// Detectors are enabled unless explicitly disabled in the config.
+fn get_enabled_detectors(config: &Config) -> Vec<CommandDetector> {
+    get_default_command_detectors()
+        .into_iter()
+        .filter(|detector| {
+            !matches!(config.dangerous_command_overrides.get(detector.rule_name), Some(false))
+        })
+        .collect()
+}
+
+/// Checks a command line against the enabled destructive command detectors.
+///
+/// # Arguments
+/// * `config` - Configuration containing detector overrides
+/// * `command` - The bash command line to check
+/// * `cwd` - Working directory the command runs in
+///
+/// # Returns
+/// * `Some(violation)` - The first detector that matches
+/// * `None` - If the command is allowed by the policy
+pub fn check_dangerous_command(config: &Config, command: &str, cwd: &Path) -> Option<PolicyViolation> {
+    let detectors = get_enabled_detectors(config);
+
+    for simple_command in shell::parse(command) {
+        let invocation = Invocation { line: command, argv: simple_command.argv(), cwd };
+
+        if let Some(detector) = detectors.iter().find(|detector| (detector.detect)(&invocation)) {
+            return Some(PolicyViolation {
+                rule_name: detector.rule_name.to_string(),
+                reason: detector.reason.to_string(),
+            });
+        }
+    }
+
+    None
+}
+
+/// Returns the arguments of `git <subcommand>`, skipping git's global options.
+fn git_args<'a, 'b>(invocation: &'b Invocation<'a>, subcommand: &str) -> Option<&'b [&'a str]> {
+    match invocation.argv.split_first() {
+        Some((&"git", args)) => shell::git_subcommand(args)
+            .filter(|(name, _)| *name == subcommand)
+            .map(|(_, rest)| rest),
+        _ => None,
+    }
+}
+
+/// Returns true if any argument is the short flag `flag` (possibly combined,
+/// like `-rf`) or one of the given long options.
+fn has_flag(args: &[&str], flag: char, long: &[&str]) -> bool {
+    args.iter().any(|arg| {
+        long.contains(arg)
+            || (arg.starts_with('-') && !arg.starts_with("--") && arg[1..].contains(flag))
+    })
+}
+
+fn detect_rm_root_or_home(invocation: &Invocation) -> bool {
+    let Some((&"rm", args)) = invocation.argv.split_first() else {
+        return false;
+    };
+
+    let recursive = has_flag(args, 'r', &["--recursive"]) || has_flag(args, 'R', &[]);
+    recursive && args.iter().any(|arg| RM_CRITICAL_TARGETS.contains(arg))
+}
+
+fn detect_git_force_push_protected(invocation: &Invocation) -> bool {
+    let Some(args) = git_args(invocation, "push") else {
+        return false;
+    };
+
+    let positional: Vec<&str> = args.iter().copied().filter(|arg| !arg.starts_with('-')).collect();
+    // `+branch` refspecs force-push a single ref
+    let forced_refspec = positional.iter().any(|refspec| refspec.starts_with('+'));
+
+    if !has_flag(args, 'f', &["--force"]) && !forced_refspec {
+        return false;
+    }
+
+    // The first positional argument is the remote; the rest are refspecs
+    let refspecs = positional.get(1..).unwrap_or_default();
+    if refspecs.is_empty() {
+        return current_branch(invocation.cwd).is_some_and(|branch| PROTECTED_BRANCHES.contains(&branch.as_str()));
+    }
+
+    refspecs.iter().any(|refspec| {
+        let target = refspec.trim_start_matches('+');
+        let target = target.rsplit_once(':').map_or(target, |(_, dst)| dst);
+        let target = target.strip_prefix("refs/heads/").unwrap_or(target);
+        PROTECTED_BRANCHES.contains(&target)
+            || (target == "HEAD"
+                && current_branch(invocation.cwd).is_some_and(|branch| PROTECTED_BRANCHES.contains(&branch.as_str())))
+    })
+}
+
+fn detect_git_reset_hard(invocation: &Invocation) -> bool {
+    git_args(invocation, "reset").is_some_and(|args| args.contains(&"--hard"))
+}
+
+fn detect_git_clean_force(invocation: &Invocation) -> bool {
+    git_args(invocation, "clean").is_some_and(|args| has_flag(args, 'f', &["--force"]))
+}
+
+fn detect_chmod_recursive_777(invocation: &Invocation) -> bool {
+    let Some((&"chmod", args)) = invocation.argv.split_first() else {
+        return false;
+    };
+
+    has_flag(args, 'R', &["--recursive"])
+        && args
+            .iter()
+            .any(|arg| matches!(*arg, "777" | "0777" | "a+rwx" | "ugo+rwx" | "a=rwx" | "ugo=rwx"))
+}
+
+fn detect_mkfs(invocation: &Invocation) -> bool {
+    invocation
+        .argv
+        .first()
+        .is_some_and(|program| *program == "mkfs" || program.starts_with("mkfs."))
+}
+
+fn detect_dd_to_device(invocation: &Invocation) -> bool {
+    let Some((&"dd", args)) = invocation.argv.split_first() else {
+        return false;
+    };
+
+    args.iter().any(|arg| {
+        arg.strip_prefix("of=/dev/")
+            .is_some_and(|device| !matches!(device, "null" | "zero" | "stdout" | "stderr"))
+    })
+}
+
+fn detect_fork_bomb(invocation: &Invocation) -> bool {
+    FORK_BOMB.captures_iter(invocation.line).any(|caps| caps[1] == caps[2] && caps[2] == caps[3])
+}
+
+/// Reads the current branch from `.git/HEAD` in `cwd` or a parent directory.
+fn current_branch(cwd: &Path) -> Option<String> {
+    let head = cwd
+        .ancestors()
+        .map(|dir| dir.join(".git/HEAD"))
+        .find(|path| path.is_file())?;
+
+    let content = fs::read_to_string(head).ok()?;
+    content.trim().strip_prefix("ref: refs/heads/").map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn check(command: &str) -> Option<String> {
+        check_dangerous_command(&Config::default(), command, Path::new("/nonexistent"))
+            .map(|violation| violation.rule_name)
+    }
+
+    #[test]
+    fn test_detects_destructive_commands() {
+        let cases = [
+            ("rm -rf /", "rm_root_or_home"),
+            ("sudo rm -fr ~", "rm_root_or_home"),
+            ("rm -r -f $HOME/*", "rm_root_or_home"),
+            ("git push --force origin main", "git_force_push_protected"),
+            ("git push origin +master", "git_force_push_protected"),
+            ("git push -f origin feature:refs/heads/main", "git_force_push_protected"),
+            ("git -C repo push --force origin main", "git_force_push_protected"),
+            ("git --no-pager -c core.editor=true reset --hard", "git_reset_hard"),
+            ("cd repo && git reset --hard HEAD~1", "git_reset_hard"),
+            ("git clean -fdx", "git_clean_force"),
+            ("chmod -R 777 .", "chmod_recursive_777"),
+            ("mkfs.ext4 /dev/sdb1", "mkfs"),
+            ("dd if=image.iso of=/dev/sda bs=4M", "dd_to_device"),
+            (":(){ :|:& };:", "fork_bomb"),
+            ("bomb() { bomb | bomb & }; bomb", "fork_bomb"),
+        ];
+
+        for (command, rule_name) in cases {
+            assert_eq!(check(command).as_deref(), Some(rule_name), "{command}");
+        }
+    }
+
+    #[test]
+    fn test_allows_safe_commands() {
+        let cases = [
+            "rm -rf ./build",
+            "rm ~/notes.txt",
+            "echo 'rm -rf /'",
+            "git push origin main",
+            "git -C push status",
+            "git push --force origin feature",
+            "git push --force-with-lease origin feature",
+            "git reset --soft HEAD~1",
+            "git clean -n",
+            "chmod 755 script.sh",
+            "chmod -R 755 public",
+            "dd if=/dev/zero of=/dev/null count=1",
+            "grep -r mkfs docs",
+        ];
+
+        for command in cases {
+            assert_eq!(check(command), None, "{command}");
+        }
+    }
+
+    #[test]
+    fn test_force_push_uses_current_branch() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp_dir.path().join(".git")).unwrap();
+        fs::write(temp_dir.path().join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        let nested = temp_dir.path().join("src");
+        fs::create_dir_all(&nested).unwrap();
+
+        let violation = check_dangerous_command(&Config::default(), "git push -f", &nested);
+        assert_eq!(violation.unwrap().rule_name, "git_force_push_protected");
+
+        fs::write(temp_dir.path().join(".git/HEAD"), "ref: refs/heads/feature\n").unwrap();
+        assert!(check_dangerous_command(&Config::default(), "git push -f", &nested).is_none());
+    }
+
+    #[test]
+    fn test_overrides_disable_detectors() {
+        let mut overrides = HashMap::new();
+        overrides.insert("git_reset_hard".to_string(), false);
+        let config = Config {
+            dangerous_command_overrides: overrides,
+            ..Default::default()
+        };
+
+        assert!(check_dangerous_command(&config, "git reset --hard", Path::new(".")).is_none());
+        assert!(check_dangerous_command(&config, "git clean -f", Path::new(".")).is_some());
+    }
+}
@@ -0,0 +1,265 @@
+//! Inline rule tests declared in the config file (`test-rules` subcommand)
+
+use crate::config::load_layered_config;
+use crate::hooks::{check_file_edit, evaluate_bash_command};
+use crate::types::{Config, CriticalAction, RuleAction, RuleTest, SecretAction, Severity};
+use anyhow::Result;
+
+/// Decisions a command test can expect
+const COMMAND_DECISIONS: &[&str] = &["allow", "suggest", "rewrite", "ask", "block", "deny"];
+
+/// Decisions a file edit test can expect
+const FILE_DECISIONS: &[&str] = &["allow", "block", "ask", "deny"];
+
+/// What the hook decided for a test input.
+#[derive(Debug, PartialEq)]
+struct Outcome {
+    decision: String,
+    rewrite: Option<String>,
+    /// Rules, policy detectors or security patterns that matched
+    rules: Vec<String>,
+}
+
+/// Runs every `[[tests]]` entry of the merged configuration and prints the results.
+///
+/// # Arguments
+/// * `config_path` - Project configuration file name
+/// * `replace_mode` - Evaluate commands as if the hook ran with `--replace`
+///
+/// # Returns
+/// * `Ok(true)` - Every test passed (or there are no tests)
+/// * `Ok(false)` - At least one test failed
+/// * `Err` - If the configuration cannot be loaded
+pub fn run_rule_tests(config_path: &str, replace_mode: bool) -> Result<bool> {
+    let config = load_layered_config(config_path, None)?;
+
+    println!("🧪 Claude Hook Advisor - Rule Tests");
+    println!("===================================");
+
+    if config.tests.is_empty() {
+        println!("No [[tests]] entries found in {config_path}.");
+        return Ok(true);
+    }
+
+    let mut failed = 0;
+    for test in &config.tests {
+        match run_test(&config, test, replace_mode)? {
+            None => println!("✓ {}", test_name(test)),
+            Some(failure) => {
+                failed += 1;
+                println!("✗ {}: {failure}", test_name(test));
+            }
+        }
+    }
+
+    println!();
+    println!("{} passed, {failed} failed", config.tests.len() - failed);
+
+    Ok(failed == 0)
+}
+
+/// Runs a single test.
+///
+/// # Returns
+/// * `Ok(None)` - The hook behaved as expected
+/// * `Ok(Some(message))` - Description of the mismatch
+/// * `Err` - If the input cannot be evaluated
+fn run_test(config: &Config, test: &RuleTest, replace_mode: bool) -> Result<Option<String>> {
+    let (outcome, decisions) = match (&test.command, &test.file) {
+        (Some(command), None) => (evaluate_command(config, command, test.cwd.as_deref(), replace_mode)?, COMMAND_DECISIONS),
+        (None, Some(file)) => (evaluate_file(config, file, &test.content)?, FILE_DECISIONS),
+        _ => return Ok(Some("set exactly one of `command` or `file`".to_string())),
+    };
+
+    if !decisions.contains(&test.expect.as_str()) {
+        return Ok(Some(format!("unknown expectation '{}' (use one of: {})", test.expect, decisions.join(", "))));
+    }
+
+    if outcome.decision != test.expect {
+        return Ok(Some(format!("expected {}, got {}", test.expect, describe(&outcome))));
+    }
+
+    if let Some(expected) = &test.rewrite {
+        if outcome.rewrite.as_ref() != Some(expected) {
+            let actual = outcome.rewrite.as_deref().map_or("no rewrite".to_string(), |rewrite| format!("'{rewrite}'"));
+            return Ok(Some(format!("expected rewrite '{expected}', got {actual}")));
+        }
+    }
+
+    if let Some(rule) = &test.rule {
+        if !outcome.rules.contains(rule) {
+            return Ok(Some(format!("expected rule '{rule}' to match, got {}", describe(&outcome))));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Evaluates a Bash command exactly as the PreToolUse hook would.
+fn evaluate_command(config: &Config, command: &str, cwd: Option<&str>, replace_mode: bool) -> Result<Outcome> {
+    let evaluation = evaluate_bash_command(config, command, cwd, replace_mode, false)?;
+    let rewritten = evaluation.rewritten_command().map(str::to_string);
+    let env_rules: Vec<String> = evaluation
+        .env
+        .as_ref()
+        .map_or(Vec::new(), |env| env.rule_ids().into_iter().map(str::to_string).collect());
+
+    if let Some(violation) = evaluation.violation {
+        return Ok(Outcome { decision: "deny".to_string(), rewrite: None, rules: vec![violation.rule_name] });
+    }
+
+    if let Some(secret) = evaluation.secret {
+        let decision = if secret.action == SecretAction::Ask { "ask" } else { "deny" };
+        return Ok(Outcome { decision: decision.to_string(), rewrite: None, rules: vec![secret.rule_name] });
+    }
+
+    let interactive_rule = evaluation.interactive.as_ref().map(|interactive| interactive.rule_name.clone());
+    let Some(mapping) = evaluation.mapping else {
+        let decision = match &evaluation.interactive {
+            Some(_) if !evaluation.rewrite => "deny",
+            Some(_) => "rewrite",
+            // Env rule variables alone are confirmed by the user
+            None if rewritten.is_some() => "ask",
+            None => "allow",
+        };
+        let rules = interactive_rule.into_iter().chain(env_rules).collect();
+        return Ok(Outcome { decision: decision.to_string(), rewrite: rewritten, rules });
+    };
+
+    let action = mapping.effective_action(replace_mode);
+    let decision = match action {
+        _ if evaluation.rewrite => "rewrite",
+        RuleAction::Suggest if evaluation.env.is_some() => "ask",
+        action => action.as_str(),
+    };
+    let mut rules: Vec<String> = interactive_rule.into_iter().collect();
+    rules.extend(mapping.matches.into_iter().map(|rule_match| rule_match.rule_id));
+    rules.extend(env_rules);
+    Ok(Outcome {
+        decision: decision.to_string(),
+        rewrite: rewritten.or(Some(mapping.replacement)),
+        rules,
+    })
+}
+
+/// Evaluates a file edit against the security patterns.
+///
+/// `warn` findings give "block", `critical` ones "deny" or "ask", and edits
+/// with only `info` findings are allowed (the findings are still listed).
+fn evaluate_file(config: &Config, file: &str, content: &str) -> Result<Outcome> {
+    let findings = check_file_edit(config, file, "", content)?;
+    let decision = match findings.iter().map(|finding| finding.severity).max() {
+        Some(Severity::Critical) if config.security_critical_action == CriticalAction::Ask => "ask",
+        Some(Severity::Critical) => "deny",
+        Some(Severity::Warn) => "block",
+        Some(Severity::Info) | None => "allow",
+    };
+    let rules = findings.into_iter().map(|finding| finding.rule_name).collect();
+    Ok(Outcome { decision: decision.to_string(), rewrite: None, rules })
+}
+
+/// Describes an outcome for failure messages, e.g. `block by 'npm' → 'bun install'`.
+fn describe(outcome: &Outcome) -> String {
+    let mut description = outcome.decision.clone();
+    if !outcome.rules.is_empty() {
+        let rules: Vec<String> = outcome.rules.iter().map(|rule| format!("'{rule}'")).collect();
+        description.push_str(&format!(" by {}", rules.join(", ")));
+    }
+    if let Some(rewrite) = &outcome.rewrite {
+        description.push_str(&format!(" → '{rewrite}'"));
+    }
+    description
+}
+
+/// Returns the name shown for a test.
+fn test_name(test: &RuleTest) -> &str {
+    test.name
+        .as_deref()
+        .or(test.command.as_deref())
+        .or(test.file.as_deref())
+        .unwrap_or("(unnamed test)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        toml::from_str(r#"
+[commands]
+npm = "bun"
+
+[[command_rules]]
+id = "no-wget"
+pattern = "wget"
+replacement = "curl -LO"
+action = "deny"
+
+[[env_rules]]
+id = "ci"
+pattern = "bun run test"
+env = { CI = "true" }
+
+[security_pattern_overrides]
+swift_force_unwrap = false
+rust_unsafe_block = "info"
+pickle_deserialization = "critical"
+"#).unwrap()
+    }
+
+    fn run(test: &str) -> Option<String> {
+        let test: RuleTest = toml::from_str(test).unwrap();
+        run_test(&config(), &test, false).unwrap()
+    }
+
+    #[test]
+    fn test_passing_command_tests() {
+        assert_eq!(run("command = 'npm install'\nexpect = 'block'\nrewrite = 'bun install'\nrule = 'npm'"), None);
+        assert_eq!(run("command = 'wget https://example.com/a.tgz'\nexpect = 'deny'\nrule = 'no-wget'"), None);
+        assert_eq!(run("command = 'git reset --hard'\nexpect = 'deny'\nrule = 'git_reset_hard'"), None);
+        assert_eq!(run("command = 'ls -la'\nexpect = 'allow'"), None);
+        assert_eq!(run("command = 'npm init'\nexpect = 'block'\nrewrite = 'bun init -y'\nrule = 'init_prompt'"), None);
+        assert_eq!(run("command = 'yarn init'\nexpect = 'rewrite'\nrewrite = 'yarn init -y'\nrule = 'init_prompt'"), None);
+        assert_eq!(run("command = 'vim notes.md'\nexpect = 'deny'\nrule = 'editor'"), None);
+        assert_eq!(run("command = 'export API_TOKEN=abcdef123456'\nexpect = 'deny'\nrule = 'secret_assignment'"), None);
+    }
+
+    #[test]
+    fn test_replace_mode_reports_rewrite() {
+        let test: RuleTest = toml::from_str("command = 'npm test'\nexpect = 'rewrite'\nrewrite = 'CI=true bun run test'").unwrap();
+        assert_eq!(run_test(&config(), &test, true).unwrap(), None);
+        assert!(run_test(&config(), &test, false).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_env_rules_report_ask() {
+        assert_eq!(run("command = 'bun run test'\nexpect = 'ask'\nrewrite = 'CI=true bun run test'\nrule = 'ci'"), None);
+        assert_eq!(run("command = 'CI=1 bun run test'\nexpect = 'allow'"), None);
+    }
+
+    #[test]
+    fn test_passing_file_tests() {
+        assert_eq!(run("file = 'src/app.js'\ncontent = 'eval(input)'\nexpect = 'block'\nrule = 'eval_injection'"), None);
+        assert_eq!(run("file = 'Sources/App.swift'\ncontent = 'let x = y!'\nexpect = 'allow'"), None);
+        assert_eq!(run("file = 'src/ffi.rs'\ncontent = 'unsafe { free(p) }'\nexpect = 'allow'\nrule = 'rust_unsafe_block'"), None);
+        assert_eq!(run("file = 'jobs.py'\ncontent = 'pickle.loads(blob)'\nexpect = 'deny'\nrule = 'pickle_deserialization'"), None);
+    }
+
+    #[test]
+    fn test_reports_mismatches() {
+        assert_eq!(
+            run("command = 'npm install'\nexpect = 'allow'").as_deref(),
+            Some("expected allow, got block by 'npm' → 'bun install'")
+        );
+        assert_eq!(
+            run("command = 'npm install'\nexpect = 'block'\nrewrite = 'bun add'").as_deref(),
+            Some("expected rewrite 'bun add', got 'bun install'")
+        );
+        assert_eq!(
+            run("command = 'ls'\nexpect = 'block'").as_deref(),
+            Some("expected block, got allow")
+        );
+        assert!(run("command = 'ls'\nexpect = 'warn'").unwrap().starts_with("unknown expectation 'warn'"));
+        assert!(run("expect = 'allow'").unwrap().contains("exactly one of"));
+    }
+}
@@ -0,0 +1,689 @@
+//! Command rule ordering, precedence and matching
+
+use crate::hooks::{get_cached_regex, glob_match};
+use crate::shell::{self, SimpleCommand};
+use crate::translate::{translate, Translation};
+use crate::types::{CommandRule, Config, RewriteRule, RuleAction, RuleCondition};
+use anyhow::{Context, Result};
+use std::cmp::Ordering;
+use std::path::Path;
+
+/// A command rule of any supported type.
+#[derive(Debug, Clone)]
+pub enum Rule {
+    /// Literal prefix swap from `[commands]` or `[[command_rules]]`
+    Command(CommandRule),
+    /// Regex rewrite from `[[rewrite_rules]]`
+    Rewrite(RewriteRule),
+}
+
+impl Rule {
+    /// Returns the identifier used in messages.
+    pub fn id(&self) -> &str {
+        match self {
+            Rule::Command(rule) => rule.id(),
+            Rule::Rewrite(rule) => rule.id(),
+        }
+    }
+
+    pub fn priority(&self) -> i32 {
+        match self {
+            Rule::Command(rule) => rule.priority,
+            Rule::Rewrite(rule) => rule.priority,
+        }
+    }
+
+    /// Returns what the hook does when the rule matches.
+    pub fn action(&self) -> RuleAction {
+        match self {
+            Rule::Command(rule) => rule.action,
+            Rule::Rewrite(rule) => rule.action,
+        }
+    }
+
+    /// Returns the rule's `when` conditions, if any.
+    pub fn when(&self) -> Option<&RuleCondition> {
+        match self {
+            Rule::Command(rule) => rule.when.as_ref(),
+            Rule::Rewrite(rule) => rule.when.as_ref(),
+        }
+    }
+
+    /// Checks whether the rule's `when` conditions hold in `cwd`.
+    ///
+    /// Rules without conditions always apply.
+    pub fn applies_in(&self, cwd: &Path) -> Result<bool> {
+        match self.when() {
+            Some(condition) => condition_holds(condition, cwd),
+            None => Ok(true),
+        }
+    }
+
+    /// Applies the rule to a simple command (starting at its command word).
+    ///
+    /// A rule whose replacement extends its pattern does not match commands
+    /// that already start with the replacement, so `curl` → `curl -L` leaves
+    /// `curl -L x` alone instead of suggesting `curl -L -L x`. Replacements
+    /// that shorten the pattern (`git commit --no-verify` → `git commit`) or
+    /// repeat it (a `deny` rule for `curl`) always match.
+    ///
+    /// # Returns
+    /// * `Ok(Some(rewritten))` - If the rule matches
+    /// * `Ok(None)` - If the rule does not match or would not change the command
+    /// * `Err` - If regex compilation fails
+    pub fn apply(&self, text: &str) -> Result<Option<String>> {
+        match self {
+            Rule::Command(rule) => {
+                let regex = literal_regex(&rule.pattern)?;
+
+                if !regex.is_match(text) {
+                    return Ok(None);
+                }
+
+                // Skip commands that already start with an extending replacement
+                let extends = rule.replacement.trim() != rule.pattern.trim() && regex.is_match(&rule.replacement);
+                if extends && literal_regex(&rule.replacement)?.is_match(text) {
+                    return Ok(None);
+                }
+
+                // Generate suggested replacement, preserving trailing whitespace
+                let rewritten = regex.replace(text, |caps: &regex::Captures| {
+                    format!("{}{}", rule.replacement, &caps[2])
+                });
+                Ok(Some(rewritten.to_string()))
+            }
+            Rule::Rewrite(rule) => {
+                let regex = get_cached_regex(&rule.pattern)?;
+
+                if !regex.is_match(text) {
+                    return Ok(None);
+                }
+
+                let rewritten = regex.replace(text, rule.replace.as_str());
+                Ok((rewritten != text).then(|| rewritten.to_string()))
+            }
+        }
+    }
+
+    /// Applies the rule to a parsed simple command of `line`.
+    ///
+    /// Like `apply`, but literal rules that swap one tool for another also
+    /// translate the command's flags through the matching profile (see
+    /// `translate`). A rule whose arguments cannot be translated faithfully
+    /// does not match, rather than suggesting a broken command.
+    pub fn apply_to(&self, line: &str, command: &SimpleCommand) -> Result<Option<String>> {
+        let Some(rewritten) = self.apply(command.command_text(line))? else {
+            return Ok(None);
+        };
+
+        let Rule::Command(rule) = self else {
+            return Ok(Some(rewritten));
+        };
+
+        // Split off redirections, which keep their raw text
+        let mut args = Vec::new();
+        let mut redirections = Vec::new();
+        let mut needs_target = false;
+        for word in &command.words[command.command_index + 1..] {
+            if needs_target || shell::is_redirection(&word.value) {
+                needs_target = !needs_target && shell::redirection_needs_target(&word.value);
+                redirections.push(&line[word.start..word.end]);
+            } else {
+                args.push(word);
+            }
+        }
+
+        let values: Vec<String> = args.iter().map(|word| word.value.clone()).collect();
+        let argv = match translate(&rule.pattern, &rule.replacement, &values) {
+            Translation::NoProfile => return Ok(Some(rewritten)),
+            Translation::Unsupported => return Ok(None),
+            Translation::Translated(argv) => argv,
+        };
+
+        // Plain swap when the profile leaves the arguments untouched, which
+        // keeps the original spacing
+        if argv[rule.replacement.split_whitespace().count()..] == values[..] {
+            return Ok(Some(rewritten));
+        }
+
+        // Replacement words are written as configured; translated arguments
+        // reuse the original quoting when unchanged
+        let replacement_len = rule.replacement.split_whitespace().count();
+        let mut parts: Vec<String> = argv[..replacement_len].to_vec();
+        parts.extend(argv[replacement_len..].iter().map(|arg| {
+            match args.iter().find(|word| &word.value == arg) {
+                Some(word) => line[word.start..word.end].to_string(),
+                None => shell::quote(arg),
+            }
+        }));
+        parts.extend(redirections.into_iter().map(str::to_string));
+
+        Ok(Some(parts.join(" ")))
+    }
+
+    /// Explains what makes the rule match, for `explain` output.
+    pub fn explain(&self) -> String {
+        let pattern = match self {
+            Rule::Command(rule) => format!("literal prefix '{}'", rule.pattern),
+            Rule::Rewrite(rule) => format!("regex '{}'", rule.pattern),
+        };
+
+        let Some(condition) = self.when() else {
+            return pattern;
+        };
+
+        let conditions = toml::to_string(condition)
+            .unwrap_or_default()
+            .lines()
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{pattern}, when {conditions}")
+    }
+
+    /// Describes a match of this rule for the suggestion message.
+    pub fn describe(&self) -> String {
+        match self {
+            Rule::Command(rule) => format!(
+                "Command '{}' is mapped to use '{}' instead.",
+                rule.pattern, rule.replacement
+            ),
+            Rule::Rewrite(_) => format!("Command matches rewrite rule '{}'.", self.id()),
+        }
+    }
+}
+
+/// Returns all command rules in the order they should be checked.
+///
+/// Combines `[[command_rules]]`, plain `[commands]` entries (priority 0) and
+/// `[[rewrite_rules]]`, and sorts them so the result is deterministic:
+/// 1. Higher `priority` first
+/// 2. Rewrite rules before literal rules, in file order
+/// 3. More specific literal patterns first (more words, then longer)
+/// 4. Literal pattern text, alphabetically
+///
+/// When the same pattern appears in both literal forms, the `[[command_rules]]`
+/// entry is checked first.
+pub fn ordered_rules(config: &Config) -> Vec<Rule> {
+    let mut plain: Vec<CommandRule> = config
+        .commands
+        .iter()
+        .map(|(pattern, replacement)| CommandRule {
+            id: None,
+            pattern: pattern.clone(),
+            replacement: replacement.clone(),
+            priority: 0,
+            when: None,
+            action: RuleAction::Block,
+        })
+        .collect();
+    plain.sort_by(|a, b| a.pattern.cmp(&b.pattern));
+
+    let mut rules: Vec<Rule> = config.rewrite_rules.iter().cloned().map(Rule::Rewrite).collect();
+    rules.extend(config.command_rules.iter().cloned().map(Rule::Command));
+    rules.extend(plain.into_iter().map(Rule::Command));
+
+    // Stable sort keeps file order for rewrite rules and table-form rules
+    // ahead of identical plain entries
+    rules.sort_by(compare_precedence);
+    rules
+}
+
+/// Returns the rules that take part in matching, in precedence order.
+///
+/// Like `ordered_rules`, but rules that form a cycle (see `find_cycles`) are
+/// left out, since following their suggestions would never settle.
+pub fn active_rules(config: &Config) -> Result<Vec<Rule>> {
+    let rules = ordered_rules(config);
+    let cyclic: Vec<usize> = cycle_indices(&rules)?.into_iter().flatten().collect();
+
+    Ok(rules
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| !cyclic.contains(index))
+        .map(|(_, rule)| rule)
+        .collect())
+}
+
+/// Checks that every rewrite rule and security pattern regex compiles and
+/// warns about cycles.
+///
+/// Called when the configuration is loaded so broken patterns are reported
+/// as configuration errors instead of failing on the first Bash command or
+/// file edit.
+pub fn validate_rules(config: &Config) -> Result<()> {
+    for rule in &config.rewrite_rules {
+        get_cached_regex(&rule.pattern)
+            .with_context(|| format!("Invalid regex in rewrite rule '{}': {}", rule.id(), rule.pattern))?;
+    }
+
+    for pattern in &config.security_patterns {
+        for regex in pattern.content_regex.iter().chain(&pattern.not_content) {
+            get_cached_regex(regex)
+                .with_context(|| format!("Invalid regex in security pattern '{}': {}", pattern.rule_name, regex))?;
+        }
+    }
+
+    for cycle in find_cycles(config)? {
+        eprintln!("Warning: Rules {} form a cycle and will be skipped.", cycle.join(" → "));
+    }
+
+    Ok(())
+}
+
+/// Finds groups of rules whose suggestions lead back to themselves.
+///
+/// Starting from each literal pattern, and from the output of each rewrite
+/// rule (with a placeholder for its capture groups), the first matching rule
+/// is applied repeatedly (ignoring `when` conditions). If a rule fires a second
+/// time, following the suggestions would never settle: `wget` → `curl -L`
+/// together with `curl` → `wget --verbose` suggests one and then the other
+/// forever, and a rewrite whose output matches itself again grows forever. A
+/// rule that leaves the command unchanged (a `deny` rule for `curl`) settles.
+///
+/// # Returns
+/// Each cycle as the ids of its rules, in the order they fire.
+pub fn find_cycles(config: &Config) -> Result<Vec<Vec<String>>> {
+    let rules = ordered_rules(config);
+    let cycles = cycle_indices(&rules)?;
+
+    Ok(cycles
+        .into_iter()
+        .map(|cycle| cycle.into_iter().map(|index| rules[index].id().to_string()).collect())
+        .collect())
+}
+
+/// Finds literal rules that can never match because an earlier rule always
+/// matches first.
+///
+/// # Returns
+/// Pairs of `(shadowed rule id, shadowing rule id)`.
+pub fn find_shadowed(config: &Config) -> Result<Vec<(String, String)>> {
+    let rules = ordered_rules(config);
+    let mut shadowed = Vec::new();
+
+    for (index, rule) in rules.iter().enumerate() {
+        let Rule::Command(later) = rule else {
+            continue;
+        };
+
+        for earlier in &rules[..index] {
+            let Rule::Command(earlier_rule) = earlier else {
+                continue;
+            };
+
+            // Conditional rules may not apply, so they shadow nothing
+            if earlier_rule.when.is_none() && literal_regex(&earlier_rule.pattern)?.is_match(&later.pattern) {
+                shadowed.push((later.id().to_string(), earlier.id().to_string()));
+                break;
+            }
+        }
+    }
+
+    Ok(shadowed)
+}
+
+/// Maximum number of suggestions followed when looking for cycles
+const MAX_CHAIN: usize = 32;
+
+/// Finds cycles as indices into `rules` (see `find_cycles`).
+fn cycle_indices(rules: &[Rule]) -> Result<Vec<Vec<usize>>> {
+    let mut cycles: Vec<Vec<usize>> = Vec::new();
+
+    for rule in rules {
+        let mut text = match rule {
+            Rule::Command(start) => start.pattern.clone(),
+            Rule::Rewrite(start) => sample_output(&start.replace)?,
+        };
+        let mut fired: Vec<usize> = Vec::new();
+
+        for _ in 0..MAX_CHAIN {
+            let Some((index, rewritten)) = first_match(rules, &text)? else {
+                break;
+            };
+            if rewritten == text {
+                break;
+            }
+
+            if let Some(position) = fired.iter().position(|&seen| seen == index) {
+                let cycle = fired.split_off(position);
+                let mut key = cycle.clone();
+                key.sort_unstable();
+                if !cycles.iter().any(|known| {
+                    let mut known = known.clone();
+                    known.sort_unstable();
+                    known == key
+                }) {
+                    cycles.push(cycle);
+                }
+                break;
+            }
+
+            fired.push(index);
+            text = rewritten;
+        }
+    }
+
+    Ok(cycles)
+}
+
+/// Builds a sample command produced by a rewrite rule's `replace` template,
+/// with `x` for each capture group reference (`$1`, `${name}`).
+fn sample_output(replace: &str) -> Result<String> {
+    let references = get_cached_regex(r"\$(\d+|\{\w+\}|[A-Za-z_]\w*)")?;
+    let sample = references.replace_all(replace, "x");
+    Ok(sample.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Applies the first matching rule to `text`.
+fn first_match(rules: &[Rule], text: &str) -> Result<Option<(usize, String)>> {
+    for (index, rule) in rules.iter().enumerate() {
+        if let Some(rewritten) = rule.apply(text)? {
+            return Ok(Some((index, rewritten)));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Regex matching a literal prefix pattern at the start of a simple command.
+///
+/// `^` anchors at the primary command position, group 1 is the pattern and
+/// group 2 (`\s|$`) requires whitespace or the end of the string after it, so
+/// subcommands and longer command names never match.
+pub(crate) fn literal_regex(pattern: &str) -> Result<regex::Regex> {
+    get_cached_regex(&format!(r"^({})(\s|$)", regex::escape(pattern)))
+}
+
+/// Evaluates `when` conditions against a working directory.
+pub(crate) fn condition_holds(condition: &RuleCondition, cwd: &Path) -> Result<bool> {
+    if let Some(file) = &condition.file_exists {
+        if !cwd.ancestors().any(|dir| dir.join(file).exists()) {
+            return Ok(false);
+        }
+    }
+
+    if let Some(program) = &condition.on_path {
+        if which::which(program).is_err() {
+            return Ok(false);
+        }
+    }
+
+    if let Some(glob) = &condition.cwd_glob {
+        // A trailing slash lets `**/web/**` match the `web` directory itself
+        let cwd = cwd.to_string_lossy();
+        if !glob_match(glob, &cwd)? && !glob_match(glob, &format!("{cwd}/"))? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Orders two rules by precedence (the rule that should win sorts first).
+fn compare_precedence(a: &Rule, b: &Rule) -> Ordering {
+    b.priority().cmp(&a.priority()).then_with(|| match (a, b) {
+        (Rule::Rewrite(_), Rule::Rewrite(_)) => Ordering::Equal,
+        (Rule::Rewrite(_), Rule::Command(_)) => Ordering::Less,
+        (Rule::Command(_), Rule::Rewrite(_)) => Ordering::Greater,
+        (Rule::Command(a), Rule::Command(b)) => specificity(&b.pattern)
+            .cmp(&specificity(&a.pattern))
+            .then_with(|| a.pattern.cmp(&b.pattern)),
+    })
+}
+
+/// Specificity of a prefix pattern: word count, then character length.
+fn specificity(pattern: &str) -> (usize, usize) {
+    (pattern.split_whitespace().count(), pattern.trim().len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn rule(pattern: &str, replacement: &str, priority: i32) -> CommandRule {
+        CommandRule {
+            id: None,
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            priority,
+            when: None,
+            action: RuleAction::Block,
+        }
+    }
+
+    #[test]
+    fn test_most_specific_plain_rule_first() {
+        let mut commands = HashMap::new();
+        commands.insert("npm".to_string(), "bun".to_string());
+        commands.insert("npm test".to_string(), "bun test".to_string());
+        commands.insert("npm run build".to_string(), "bun run build".to_string());
+        let config = Config { commands, ..Default::default() };
+
+        let patterns: Vec<String> = ordered_rules(&config)
+            .iter()
+            .map(|rule| rule.id().to_string())
+            .collect();
+
+        assert_eq!(patterns, vec!["npm run build", "npm test", "npm"]);
+    }
+
+    #[test]
+    fn test_priority_overrides_specificity() {
+        let mut commands = HashMap::new();
+        commands.insert("npm test".to_string(), "bun test".to_string());
+        let config = Config {
+            commands,
+            command_rules: vec![rule("npm", "pnpm", 10)],
+            ..Default::default()
+        };
+
+        let rules = ordered_rules(&config);
+        assert_eq!(rules[0].id(), "npm");
+        assert_eq!(rules[0].apply("npm test").unwrap().unwrap(), "pnpm test");
+    }
+
+    #[test]
+    fn test_table_rule_wins_over_identical_plain_entry() {
+        let mut commands = HashMap::new();
+        commands.insert("npm".to_string(), "bun".to_string());
+        let config = Config {
+            commands,
+            command_rules: vec![rule("npm", "pnpm", 0)],
+            ..Default::default()
+        };
+
+        let rules = ordered_rules(&config);
+        assert_eq!(rules[0].apply("npm").unwrap().unwrap(), "pnpm");
+        assert_eq!(rules[1].apply("npm").unwrap().unwrap(), "bun");
+    }
+
+    #[test]
+    fn test_command_rules_table_parsing() {
+        let config: Config = toml::from_str(r#"
+[commands]
+npm = "bun"
+
+[[command_rules]]
+id = "prefer-yarn-tests"
+pattern = "npm test"
+replacement = "yarn test"
+priority = 5
+"#).unwrap();
+
+        assert_eq!(config.command_rules.len(), 1);
+        assert_eq!(config.command_rules[0].id(), "prefer-yarn-tests");
+        assert_eq!(config.command_rules[0].priority, 5);
+    }
+
+    #[test]
+    fn test_rewrite_rules_with_capture_groups() {
+        let config: Config = toml::from_str(r#"
+[[rewrite_rules]]
+id = "npm-run-to-bun"
+match = '^npm run (\w+)(.*)$'
+replace = 'bun run $1$2'
+
+[[rewrite_rules]]
+id = "drop-verbose"
+match = '^(git \w+) --verbose(.*)$'
+replace = '$1$2'
+"#).unwrap();
+        validate_rules(&config).unwrap();
+
+        let rules = ordered_rules(&config);
+        assert_eq!(rules[0].id(), "npm-run-to-bun");
+        assert_eq!(rules[0].apply("npm run build --prod").unwrap().unwrap(), "bun run build --prod");
+        assert!(rules[0].apply("npm install").unwrap().is_none());
+        assert_eq!(rules[1].apply("git push --verbose origin").unwrap().unwrap(), "git push origin");
+    }
+
+    #[test]
+    fn test_applied_replacement_does_not_match_again() {
+        let mut commands = HashMap::new();
+        commands.insert("curl".to_string(), "curl -L".to_string());
+        let config = Config { commands, ..Default::default() };
+
+        let rules = ordered_rules(&config);
+        assert_eq!(rules[0].apply("curl x").unwrap().unwrap(), "curl -L x");
+        assert!(rules[0].apply("curl -L x").unwrap().is_none());
+        assert!(find_cycles(&config).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_shortening_and_repeating_replacements_match() {
+        let config: Config = toml::from_str(r#"
+[commands]
+"git commit --no-verify" = "git commit"
+
+[[command_rules]]
+pattern = "curl"
+replacement = "curl"
+action = "deny"
+"#).unwrap();
+
+        let rules = ordered_rules(&config);
+        assert_eq!(rules[0].apply("git commit --no-verify -m x").unwrap().unwrap(), "git commit -m x");
+        assert_eq!(rules[1].apply("curl https://x.sh").unwrap().unwrap(), "curl https://x.sh");
+        assert!(find_cycles(&config).unwrap().is_empty());
+        assert_eq!(active_rules(&config).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_self_matching_rewrite_rule_is_a_cycle() {
+        let config: Config = toml::from_str(r#"
+[[rewrite_rules]]
+id = "silent"
+match = '^npm run (\w+)(.*)$'
+replace = 'npm run $1 --silent$2'
+
+[[rewrite_rules]]
+id = "npm-run-to-bun"
+match = '^npm test(.*)$'
+replace = 'bun run test$1'
+"#).unwrap();
+
+        assert_eq!(find_cycles(&config).unwrap(), vec![vec!["silent".to_string()]]);
+        let active: Vec<String> = active_rules(&config).unwrap().iter().map(|rule| rule.id().to_string()).collect();
+        assert_eq!(active, vec!["npm-run-to-bun"]);
+    }
+
+    #[test]
+    fn test_cyclic_rules_are_skipped() {
+        let mut commands = HashMap::new();
+        commands.insert("wget".to_string(), "curl -L".to_string());
+        commands.insert("curl".to_string(), "wget --verbose".to_string());
+        commands.insert("npm".to_string(), "bun".to_string());
+        let config = Config { commands, ..Default::default() };
+
+        let cycles = find_cycles(&config).unwrap();
+        assert_eq!(cycles.len(), 1);
+        let mut ids = cycles[0].clone();
+        ids.sort();
+        assert_eq!(ids, vec!["curl", "wget"]);
+
+        let active: Vec<String> = active_rules(&config).unwrap().iter().map(|rule| rule.id().to_string()).collect();
+        assert_eq!(active, vec!["npm"]);
+    }
+
+    #[test]
+    fn test_when_conditions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let web = temp_dir.path().join("web/src");
+        std::fs::create_dir_all(&web).unwrap();
+        std::fs::write(temp_dir.path().join("web/bun.lockb"), "").unwrap();
+
+        let lockfile = RuleCondition { file_exists: Some("bun.lockb".to_string()), ..Default::default() };
+        assert!(condition_holds(&lockfile, &web).unwrap());
+        assert!(!condition_holds(&lockfile, temp_dir.path()).unwrap());
+
+        let glob = RuleCondition { cwd_glob: Some("**/web/**".to_string()), ..Default::default() };
+        assert!(condition_holds(&glob, &web).unwrap());
+        assert!(condition_holds(&glob, &temp_dir.path().join("web")).unwrap());
+        assert!(!condition_holds(&glob, temp_dir.path()).unwrap());
+
+        let missing = RuleCondition { on_path: Some("no-such-binary-for-tests".to_string()), ..Default::default() };
+        assert!(!condition_holds(&missing, &web).unwrap());
+
+        // All conditions must hold
+        let both = RuleCondition { on_path: Some("no-such-binary-for-tests".to_string()), ..lockfile };
+        assert!(!condition_holds(&both, &web).unwrap());
+    }
+
+    #[test]
+    fn test_rewrite_rules_before_equal_priority_literals() {
+        let mut commands = HashMap::new();
+        commands.insert("npm run".to_string(), "yarn run".to_string());
+        let config = Config {
+            commands,
+            rewrite_rules: vec![RewriteRule {
+                id: None,
+                pattern: "^npm run (\\w+)$".to_string(),
+                replace: "bun run $1".to_string(),
+                priority: 0,
+                when: None,
+                action: RuleAction::Block,
+            }],
+            ..Default::default()
+        };
+
+        let rules = ordered_rules(&config);
+        assert!(matches!(rules[0], Rule::Rewrite(_)));
+        assert_eq!(rules[0].id(), "^npm run (\\w+)$");
+    }
+
+    #[test]
+    fn test_invalid_rewrite_pattern_is_config_error() {
+        let config: Config = toml::from_str(r#"
+[[rewrite_rules]]
+id = "broken"
+match = '^npm run (\w+'
+replace = 'bun run $1'
+"#).unwrap();
+
+        let err = validate_rules(&config).unwrap_err();
+        assert!(format!("{err:#}").contains("Invalid regex in rewrite rule 'broken'"));
+    }
+
+    #[test]
+    fn test_invalid_security_pattern_regex_is_config_error() {
+        let config: Config = toml::from_str(r#"
+[[security_patterns]]
+rule_name = "broken_call"
+content_regex = ['foo(']
+reminder = "Don't call foo."
+"#).unwrap();
+
+        let err = validate_rules(&config).unwrap_err();
+        assert!(format!("{err:#}").contains("Invalid regex in security pattern 'broken_call': foo("));
+
+        let config: Config = toml::from_str(r#"
+[[security_patterns]]
+rule_name = "broken_exclusion"
+content_substrings = ["foo("]
+not_content = ['^\s*(//']
+reminder = "Don't call foo."
+"#).unwrap();
+        assert!(validate_rules(&config).is_err());
+    }
+}
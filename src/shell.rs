@@ -0,0 +1,542 @@
+//! Shell-aware command line parsing
+//!
+//! Splits a Bash command line into simple commands (separated by `&&`, `||`,
+//! `;`, `|`, `&`, newlines and subshell boundaries) and locates the actual
+//! command word in each one, skipping environment assignments and wrappers
+//! such as `sudo`, `time` and `nohup`. Command substitutions (`$(...)` and
+//! backticks, also inside double quotes) are parsed as commands of their own
+//! and stay part of the word they appear in, so the enclosing command keeps
+//! its arguments. All positions are byte offsets into the original string so
+//! rewrites can be spliced back without disturbing the rest of the command
+//! line. Here-document bodies are skipped.
+
+use std::ops::Range;
+
+/// A whitespace-separated word with quotes and escapes removed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Word {
+    /// Byte range of the raw word in the command line
+    pub start: usize,
+    pub end: usize,
+    /// Unquoted value
+    pub value: String,
+}
+
+/// A simple command within a (possibly compound) command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimpleCommand {
+    /// Byte range of the whole simple command, including prefixes
+    pub start: usize,
+    pub end: usize,
+    /// Byte offset where the command word begins (after assignments and wrappers)
+    pub command_start: usize,
+    /// All words of the simple command, including prefixes
+    pub words: Vec<Word>,
+    /// Index into `words` of the command word (`words.len()` if there is none)
+    pub command_index: usize,
+}
+
+impl SimpleCommand {
+    /// Returns the text from the command word to the end of the simple command.
+    pub fn command_text<'a>(&self, line: &'a str) -> &'a str {
+        &line[self.command_start..self.end]
+    }
+
+    /// Returns the unquoted words from the command word onwards.
+    pub fn argv(&self) -> Vec<&str> {
+        self.words[self.command_index..].iter().map(|word| word.value.as_str()).collect()
+    }
+
+    /// Returns the byte range from the command word to the end.
+    pub fn command_range(&self) -> Range<usize> {
+        self.command_start..self.end
+    }
+}
+
+/// Wrapper commands that run the following command word.
+const WRAPPERS: &[&str] = &["sudo", "time", "nohup", "env"];
+
+/// Reserved words that may precede a command.
+const RESERVED_PREFIXES: &[&str] = &["!", "{", "if", "then", "else", "elif", "do", "while", "until"];
+
+/// `sudo` options that consume the following word as their argument.
+const SUDO_OPTIONS_WITH_ARGUMENT: &[&str] = &["-u", "-g", "-C", "-h", "-p", "-r", "-t", "-U", "-D", "-R", "-T"];
+
+/// Splits a command line into its simple commands.
+///
+/// Quoted text is never split, so `echo "a && b"` is a single simple command.
+/// Segments that contain no words (e.g. between `;;`) are skipped. Commands
+/// inside a substitution are returned alongside the command containing it,
+/// in order of where they start.
+pub fn parse(line: &str) -> Vec<SimpleCommand> {
+    let mut parser = Parser::default();
+    let mut chars = line.char_indices().peekable();
+    let mut quote: Option<char> = None;
+
+    while let Some((i, c)) = chars.next() {
+        if let Some(open) = quote {
+            let next = chars.peek().map(|&(_, next)| next);
+            if open == '"' && c == '$' && next == Some('(') {
+                chars.next();
+                parser.open_group(GroupKind::Substitution, i, quote.take());
+                continue;
+            } else if open == '"' && c == '`' {
+                parser.open_group(GroupKind::Backticks, i, quote.take());
+                continue;
+            } else if c == open {
+                quote = None;
+            } else if open == '"' && c == '\\' {
+                match chars.next() {
+                    Some((_, next)) if matches!(next, '"' | '\\' | '$' | '`') => parser.push(i, next),
+                    Some((_, next)) => {
+                        parser.push(i, '\\');
+                        parser.push(i, next);
+                    }
+                    None => parser.push(i, '\\'),
+                }
+            } else {
+                parser.push(i, c);
+            }
+            parser.extend_to(i + c.len_utf8());
+            continue;
+        }
+
+        let next = chars.peek().map(|&(_, next)| next);
+        match c {
+            '\'' | '"' => {
+                parser.begin(i);
+                quote = Some(c);
+            }
+            '\\' => {
+                parser.begin(i);
+                match chars.next() {
+                    // Line continuation
+                    Some((_, '\n')) => {}
+                    Some((j, escaped)) => {
+                        parser.push(j, escaped);
+                        parser.extend_to(j + escaped.len_utf8());
+                    }
+                    None => parser.push(i, '\\'),
+                }
+            }
+            ' ' | '\t' | '\r' => parser.finish_word(),
+            '#' if !parser.in_word() => {
+                // Comment: skip to the end of the line
+                while let Some(&(_, next)) = chars.peek() {
+                    if next == '\n' {
+                        break;
+                    }
+                    chars.next();
+                }
+            }
+            '\n' => {
+                parser.finish_command();
+                // Skip here-document bodies up to each delimiter line
+                for delimiter in std::mem::take(&mut parser.heredocs) {
+                    loop {
+                        let body_line: String = chars.by_ref().map(|(_, c)| c).take_while(|&c| c != '\n').collect();
+                        if body_line.trim_start_matches('\t') == delimiter || chars.peek().is_none() {
+                            break;
+                        }
+                    }
+                }
+            }
+            ';' => parser.finish_command(),
+            '(' => {
+                parser.finish_command();
+                parser.open_group(GroupKind::Subshell, i, None);
+            }
+            ')' if parser.in_group(&[GroupKind::Subshell, GroupKind::Substitution]) => {
+                quote = parser.close_group(line, i + 1);
+            }
+            '`' if parser.in_group(&[GroupKind::Backticks]) => quote = parser.close_group(line, i + 1),
+            '`' => parser.open_group(GroupKind::Backticks, i, None),
+            ')' => parser.finish_command(),
+            '|' => {
+                if matches!(next, Some('|') | Some('&')) {
+                    chars.next();
+                }
+                parser.finish_command();
+            }
+            '&' => {
+                if next == Some('&') {
+                    chars.next();
+                    parser.finish_command();
+                } else if next == Some('>') || parser.word_ends_with_redirect() {
+                    // `&>file`, `2>&1`: part of a redirection word
+                    parser.push(i, c);
+                } else {
+                    // Background operator
+                    parser.finish_command();
+                }
+            }
+            '$' if next == Some('(') => {
+                chars.next();
+                parser.open_group(GroupKind::Substitution, i, None);
+            }
+            _ => parser.push(i, c),
+        }
+    }
+
+    // Unterminated groups run to the end of the line
+    while !parser.groups.is_empty() {
+        parser.close_group(line, line.len());
+    }
+    parser.finish_command();
+    parser.commands.sort_by_key(|command| command.start);
+    parser.commands
+}
+
+/// Applies replacements to byte ranges of `line`.
+///
+/// Ranges may be given in any order. A range that overlaps an earlier one,
+/// such as a command inside a substitution of a command that is replaced as a
+/// whole, is skipped.
+pub fn splice(line: &str, edits: &[(Range<usize>, String)]) -> String {
+    let mut sorted: Vec<&(Range<usize>, String)> = edits.iter().collect();
+    sorted.sort_by_key(|(range, _)| range.start);
+
+    let mut result = String::with_capacity(line.len());
+    let mut position = 0;
+    for (range, replacement) in sorted {
+        if range.start < position {
+            continue;
+        }
+        result.push_str(&line[position..range.start]);
+        result.push_str(replacement);
+        position = range.end;
+    }
+    result.push_str(&line[position..]);
+    result
+}
+
+/// Quotes a word for the shell if it contains special characters.
+pub fn quote(word: &str) -> String {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || "-_./=:,+@%^".contains(c);
+    if !word.is_empty() && word.chars().all(is_safe) {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+/// Returns true if the word is a redirection such as `2>&1`, `>out` or `<in`.
+pub fn is_redirection(word: &str) -> bool {
+    let operator = word.trim_start_matches(|c: char| c.is_ascii_digit());
+    operator.starts_with('>') || operator.starts_with('<') || operator.starts_with("&>")
+}
+
+/// Returns true if a redirection word still needs its target (e.g. `2>`).
+pub fn redirection_needs_target(word: &str) -> bool {
+    is_redirection(word) && word.ends_with(['>', '<'])
+}
+
+/// Returns true if the word is a `NAME=value` environment assignment.
+pub fn is_assignment(word: &str) -> bool {
+    let Some((name, _)) = word.split_once('=') else {
+        return false;
+    };
+
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Splits the arguments of a `git` invocation into the subcommand and its
+/// arguments, skipping global options such as `-C dir`.
+pub fn git_subcommand<'a, 'b>(args: &'b [&'a str]) -> Option<(&'a str, &'b [&'a str])> {
+    let mut i = 0;
+    while let Some(&arg) = args.get(i) {
+        match arg {
+            // Global options that take a separate value
+            "-C" | "-c" | "--git-dir" | "--work-tree" | "--namespace" => i += 2,
+            _ if arg.starts_with('-') => i += 1,
+            _ => return Some((arg, &args[i + 1..])),
+        }
+    }
+    None
+}
+
+/// Finds the index of the command word, skipping assignments and wrappers.
+fn command_word_index(words: &[Word]) -> usize {
+    let mut i = 0;
+
+    while i < words.len() {
+        let word = words[i].value.as_str();
+
+        if is_assignment(word) || RESERVED_PREFIXES.contains(&word) {
+            i += 1;
+            continue;
+        }
+
+        if !WRAPPERS.contains(&word) {
+            break;
+        }
+
+        i += 1;
+        while i < words.len() && words[i].value.starts_with('-') {
+            let option = words[i].value.as_str();
+            i += 1;
+            if word == "sudo" && SUDO_OPTIONS_WITH_ARGUMENT.contains(&option) {
+                i += 1;
+            }
+        }
+    }
+
+    i.min(words.len())
+}
+
+/// Kind of bracketed group in a command line.
+#[derive(Clone, Copy, PartialEq)]
+enum GroupKind {
+    /// `( ... )`
+    Subshell,
+    /// `$( ... )`
+    Substitution,
+    /// `` `...` ``
+    Backticks,
+}
+
+/// A group being parsed, with the state of the command around it.
+struct Group {
+    kind: GroupKind,
+    /// Byte offset of the opening `(`, `$(` or backtick
+    start: usize,
+    /// Words of the enclosing command, restored when the group ends
+    words: Vec<Word>,
+    word: Option<Word>,
+    /// Quote the group was opened in (`"` for `"$(...)"`)
+    quote: Option<char>,
+}
+
+/// Incremental state for `parse`.
+#[derive(Default)]
+struct Parser {
+    commands: Vec<SimpleCommand>,
+    words: Vec<Word>,
+    word: Option<Word>,
+    /// Here-document delimiters whose bodies start after the next newline
+    heredocs: Vec<String>,
+    /// Open groups, innermost last
+    groups: Vec<Group>,
+}
+
+impl Parser {
+    fn in_group(&self, kinds: &[GroupKind]) -> bool {
+        self.groups.last().is_some_and(|group| kinds.contains(&group.kind))
+    }
+
+    /// Starts a group; a substitution sets the enclosing command aside.
+    fn open_group(&mut self, kind: GroupKind, start: usize, quote: Option<char>) {
+        let (words, word) = match kind {
+            GroupKind::Subshell => (Vec::new(), None),
+            _ => (std::mem::take(&mut self.words), self.word.take()),
+        };
+        self.groups.push(Group { kind, start, words, word, quote });
+    }
+
+    /// Ends the innermost group at byte offset `end`.
+    ///
+    /// The commands inside are finished. A substitution's raw text becomes
+    /// part of the enclosing command's current word.
+    ///
+    /// # Returns
+    /// The quote the group was opened in, which is in effect again
+    fn close_group(&mut self, line: &str, end: usize) -> Option<char> {
+        self.finish_command();
+        let group = self.groups.pop()?;
+        if group.kind == GroupKind::Subshell {
+            return None;
+        }
+
+        self.words = group.words;
+        self.word = group.word;
+        self.begin(group.start);
+        if let Some(word) = &mut self.word {
+            word.value.push_str(&line[group.start..end]);
+        }
+        self.extend_to(end);
+        group.quote
+    }
+
+    fn in_word(&self) -> bool {
+        self.word.is_some()
+    }
+
+    /// Starts a word at `start` if one is not already in progress.
+    fn begin(&mut self, start: usize) {
+        if self.word.is_none() {
+            self.word = Some(Word { start, end: start + 1, value: String::new() });
+        }
+    }
+
+    /// Extends the current word so its raw range ends at `end`.
+    fn extend_to(&mut self, end: usize) {
+        if let Some(word) = &mut self.word {
+            word.end = word.end.max(end);
+        }
+    }
+
+    /// Appends an unquoted character to the current word.
+    fn push(&mut self, index: usize, c: char) {
+        self.begin(index);
+        if let Some(word) = &mut self.word {
+            word.value.push(c);
+            word.end = word.end.max(index + c.len_utf8());
+        }
+    }
+
+    fn word_ends_with_redirect(&self) -> bool {
+        self.word
+            .as_ref()
+            .is_some_and(|word| word.value.ends_with('>') || word.value.ends_with('<'))
+    }
+
+    fn finish_word(&mut self) {
+        if let Some(word) = self.word.take() {
+            let previous = self.words.last().map(|previous| previous.value.as_str());
+            if matches!(previous, Some("<<") | Some("<<-")) {
+                self.heredocs.push(word.value.clone());
+            } else if let Some(delimiter) = word.value.strip_prefix("<<").filter(|rest| !rest.starts_with('<')) {
+                let delimiter = delimiter.strip_prefix('-').unwrap_or(delimiter);
+                if !delimiter.is_empty() {
+                    self.heredocs.push(delimiter.to_string());
+                }
+            }
+            self.words.push(word);
+        }
+    }
+
+    fn finish_command(&mut self) {
+        self.finish_word();
+        let words = std::mem::take(&mut self.words);
+        let (Some(first), Some(last)) = (words.first(), words.last()) else {
+            return;
+        };
+
+        let command_index = command_word_index(&words);
+        let command_start = words.get(command_index).map_or(last.end, |word| word.start);
+
+        self.commands.push(SimpleCommand {
+            start: first.start,
+            end: last.end,
+            command_start,
+            command_index,
+            words,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command_texts(line: &str) -> Vec<&str> {
+        parse(line).iter().map(|cmd| cmd.command_text(line)).collect()
+    }
+
+    #[test]
+    fn test_splits_compound_commands() {
+        assert_eq!(
+            command_texts("cd web && npm install || echo failed; ls | wc -l"),
+            vec!["cd web", "npm install", "echo failed", "ls", "wc -l"]
+        );
+        assert_eq!(command_texts("(cd web; npm test) & sleep 1"), vec!["cd web", "npm test", "sleep 1"]);
+        assert_eq!(command_texts("echo $(npm bin)/x"), vec!["echo $(npm bin)/x", "npm bin"]);
+    }
+
+    #[test]
+    fn test_substitutions_are_nested_commands() {
+        // Words after a substitution still belong to the enclosing command
+        let line = "echo $(date) npm install";
+        assert_eq!(command_texts(line), vec!["echo $(date) npm install", "date"]);
+        assert_eq!(parse(line)[0].argv(), vec!["echo", "$(date)", "npm", "install"]);
+
+        let line = r#"rm -rf "$(pwd)"/x"#;
+        assert_eq!(command_texts(line), vec![r#"rm -rf "$(pwd)"/x"#, "pwd"]);
+        assert_eq!(parse(line)[0].argv(), vec!["rm", "-rf", "$(pwd)/x"]);
+
+        assert_eq!(command_texts("ls `npm root -g` | wc -l"), vec!["ls `npm root -g`", "npm root -g", "wc -l"]);
+        assert_eq!(command_texts(r#"echo "v=`git describe`" done"#), vec![r#"echo "v=`git describe`" done"#, "git describe"]);
+        assert_eq!(command_texts("echo $(cd web && npm ls) x"), vec!["echo $(cd web && npm ls) x", "cd web", "npm ls"]);
+        assert_eq!(command_texts("echo $(npm bin"), vec!["echo $(npm bin", "npm bin"]);
+    }
+
+    #[test]
+    fn test_skips_assignments_and_wrappers() {
+        assert_eq!(command_texts("FOO=1 BAR='a b' npm test"), vec!["npm test"]);
+        assert_eq!(command_texts("sudo -u root rm x"), vec!["rm x"]);
+        assert_eq!(command_texts("time npm run build"), vec!["npm run build"]);
+        assert_eq!(command_texts("nohup env CI=true npm start &"), vec!["npm start"]);
+    }
+
+    #[test]
+    fn test_quotes_are_not_split() {
+        let line = r#"echo "a && npm i" 'b; c' d\;e"#;
+        let commands = parse(line);
+        assert_eq!(commands.len(), 1);
+        let values: Vec<&str> = commands[0].words.iter().map(|word| word.value.as_str()).collect();
+        assert_eq!(values, vec!["echo", "a && npm i", "b; c", "d;e"]);
+    }
+
+    #[test]
+    fn test_redirections_are_not_separators() {
+        assert_eq!(command_texts("npm test 2>&1 | tee log"), vec!["npm test 2>&1", "tee log"]);
+        assert_eq!(command_texts("npm test &> log"), vec!["npm test &> log"]);
+    }
+
+    #[test]
+    fn test_comments_and_empty_segments() {
+        assert_eq!(command_texts("npm test # && rm -rf /"), vec!["npm test"]);
+        assert_eq!(command_texts(";; ls ;"), vec!["ls"]);
+        assert!(parse("").is_empty());
+        assert_eq!(parse("FOO=1")[0].command_text("FOO=1"), "");
+    }
+
+    #[test]
+    fn test_splice_preserves_surrounding_text() {
+        let line = "cd web && npm install && npm test";
+        let edits: Vec<(Range<usize>, String)> = parse(line)
+            .iter()
+            .filter(|cmd| cmd.words[cmd.command_index].value == "npm")
+            .map(|cmd| (cmd.command_start..cmd.command_start + 3, "bun".to_string()))
+            .collect();
+
+        assert_eq!(splice(line, &edits), "cd web && bun install && bun test");
+    }
+
+    #[test]
+    fn test_heredoc_bodies_are_skipped() {
+        let line = "cat <<'EOF' > notes\nnpm install\nEOF\nnpm test";
+        assert_eq!(command_texts(line), vec!["cat <<'EOF' > notes", "npm test"]);
+        assert_eq!(command_texts("cat <<- END\n\tls\n\tEND\nls -l"), vec!["cat <<- END", "ls -l"]);
+        assert_eq!(command_texts("cat <<<hello; ls"), vec!["cat <<<hello", "ls"]);
+    }
+
+    #[test]
+    fn test_quote_and_redirections() {
+        assert_eq!(quote("src/main.rs"), "src/main.rs");
+        assert_eq!(quote("*.rs"), "'*.rs'");
+        assert_eq!(quote("it's"), r"'it'\''s'");
+        assert!(is_redirection("2>&1"));
+        assert!(is_redirection(">>log"));
+        assert!(!is_redirection("-n"));
+        assert!(redirection_needs_target("2>"));
+        assert!(!redirection_needs_target("2>/dev/null"));
+    }
+
+    #[test]
+    fn test_git_subcommand() {
+        assert_eq!(git_subcommand(&["push", "-f"]), Some(("push", &["-f"][..])));
+        assert_eq!(git_subcommand(&["-C", "repo", "--no-pager", "-c", "a=b", "log"]), Some(("log", &[][..])));
+        assert_eq!(git_subcommand(&["--version"]), None);
+    }
+
+    #[test]
+    fn test_is_assignment() {
+        assert!(is_assignment("CI=true"));
+        assert!(is_assignment("_X="));
+        assert!(!is_assignment("1X=true"));
+        assert!(!is_assignment("--flag=value"));
+    }
+}
@@ -0,0 +1,392 @@
+//! Command mapping suggestions mined from history (`suggest-mappings` subcommand)
+//!
+//! Looks for the pattern where Claude runs `X args`, the command fails, and
+//! the next command in the same session and working directory is `Y args`,
+//! which succeeds. Each such pair is a candidate `X → Y` mapping.
+
+use crate::config::{discover_config_layers, load_layered_config};
+use crate::history::{self, CommandRecord, HistoryQuery};
+use crate::hooks::{check_command_mappings, expand_tilde};
+use crate::shell::{self, SimpleCommand};
+use crate::types::{CommandRule, Config, RuleAction};
+use anyhow::{bail, Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A mapping seen in history, ranked by how often it occurred.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MappingCandidate {
+    pub from: String,
+    pub to: String,
+    /// Number of failed → successful command pairs
+    pub count: usize,
+    /// Number of distinct sessions the pair occurred in
+    pub sessions: usize,
+    /// First failed command and the command that replaced it
+    pub example: (String, String),
+}
+
+/// Prints mapping suggestions from the command history and optionally
+/// appends the chosen ones to the project configuration.
+///
+/// # Arguments
+/// * `config_path` - Project configuration file name
+/// * `min_count` - Only show candidates seen at least this many times
+/// * `apply` - Suggestions to append: `all` or comma-separated numbers
+pub fn run_suggest_mappings(config_path: &str, min_count: usize, apply: Option<&str>) -> Result<()> {
+    let config = load_layered_config(config_path, None)?;
+
+    let Some(db_path) = history_database(&config)? else {
+        println!("Command history is not enabled; add a [command_history] section to collect it.");
+        return Ok(());
+    };
+
+    if !db_path.exists() {
+        println!("No command history found at: {}", db_path.display());
+        return Ok(());
+    }
+
+    let conn = history::init_database(&db_path)
+        .context("Failed to open command history database")?;
+    let mut records = history::query_history(&conn, &HistoryQuery::default())
+        .context("Failed to query command history")?;
+    // Oldest first, so each command is followed by the one Claude ran next
+    records.reverse();
+
+    let candidates: Vec<MappingCandidate> = mine_candidates(&records)
+        .into_iter()
+        .filter(|candidate| candidate.count >= min_count)
+        .filter(|candidate| !is_mapped(&config, &candidate.from))
+        .collect();
+
+    println!("💡 Claude Hook Advisor - Suggested Mappings");
+    println!("===========================================");
+
+    if candidates.is_empty() {
+        println!("No new mappings found in {} recorded commands.", records.len());
+        return Ok(());
+    }
+
+    for (index, candidate) in candidates.iter().enumerate() {
+        println!(
+            "{:>2}. {} → {}  (seen {} time(s) in {} session(s))",
+            index + 1,
+            candidate.from,
+            candidate.to,
+            candidate.count,
+            candidate.sessions
+        );
+        println!("    e.g. {} → {}", candidate.example.0, candidate.example.1);
+    }
+
+    let Some(apply) = apply else {
+        println!();
+        println!("Append mappings to your config with: claude-hook-advisor suggest-mappings --apply 1,2");
+        return Ok(());
+    };
+
+    let chosen = select_candidates(&candidates, apply)?;
+    let target = project_config_path(config_path);
+    append_mappings(&target, &chosen)?;
+
+    println!();
+    println!("✅ Added {} mapping(s) to {}", chosen.len(), target.display());
+    Ok(())
+}
+
+/// Finds candidate mappings in chronologically ordered history records.
+///
+/// Candidates are sorted by frequency, then by number of sessions.
+fn mine_candidates(records: &[CommandRecord]) -> Vec<MappingCandidate> {
+    let mut previous: HashMap<(&str, Option<&str>), &CommandRecord> = HashMap::new();
+    let mut found: Vec<(MappingCandidate, HashSet<&str>)> = Vec::new();
+
+    for record in records {
+        let key = (record.session_id.as_str(), record.cwd.as_deref());
+
+        if let Some(failed) = previous.insert(key, record) {
+            if !is_failure(failed) || is_failure(record) {
+                continue;
+            }
+
+            let Some((from, to)) = mapping_between(&failed.command, &record.command) else {
+                continue;
+            };
+
+            match found.iter_mut().find(|(candidate, _)| candidate.from == from && candidate.to == to) {
+                Some((candidate, sessions)) => {
+                    candidate.count += 1;
+                    sessions.insert(&record.session_id);
+                }
+                None => found.push((
+                    MappingCandidate {
+                        from,
+                        to,
+                        count: 1,
+                        sessions: 0,
+                        example: (failed.command.clone(), record.command.clone()),
+                    },
+                    HashSet::from([record.session_id.as_str()]),
+                )),
+            }
+        }
+    }
+
+    let mut candidates: Vec<MappingCandidate> = found
+        .into_iter()
+        .map(|(candidate, sessions)| MappingCandidate { sessions: sessions.len(), ..candidate })
+        .collect();
+
+    candidates.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then(b.sessions.cmp(&a.sessions))
+            .then(a.from.cmp(&b.from))
+    });
+    candidates
+}
+
+/// Returns true if a history record ran but did not complete successfully.
+///
+/// Commands the hook blocked ("mapped" or "denied") never ran, so they are not
+/// failures.
+fn is_failure(record: &CommandRecord) -> bool {
+    matches!(record.status.as_str(), "pending" | "failed") || record.exit_code.is_some_and(|code| code != 0)
+}
+
+/// Derives the mapping that turns a failed command into the successful one.
+///
+/// Both command lines must have the same shape and differ in exactly one
+/// simple command. Shared trailing arguments are dropped, so `npm install`
+/// followed by `bun install` gives `npm → bun`. Unrelated commands (no shared
+/// arguments, and not a single word or flags appended) give `None`, as do
+/// replacements that append positional arguments: as a prefix rule they would
+/// land in front of the command's own arguments.
+fn mapping_between(failed: &str, succeeded: &str) -> Option<(String, String)> {
+    let failed_commands = shell::parse(failed);
+    let succeeded_commands = shell::parse(succeeded);
+    if failed_commands.len() != succeeded_commands.len() {
+        return None;
+    }
+
+    let differing: Vec<(&SimpleCommand, &SimpleCommand)> = failed_commands
+        .iter()
+        .zip(&succeeded_commands)
+        .filter(|(a, b)| a.command_text(failed) != b.command_text(succeeded))
+        .collect();
+    let [(from_command, to_command)] = differing[..] else {
+        return None;
+    };
+
+    let from_words = raw_words(failed, from_command);
+    let to_words = raw_words(succeeded, to_command);
+    if from_words.is_empty() || to_words.is_empty() {
+        return None;
+    }
+
+    // Both commands always keep at least their command word
+    let common = (0..from_words.len().min(to_words.len()) - 1)
+        .take_while(|&i| from_words[from_words.len() - 1 - i] == to_words[to_words.len() - 1 - i])
+        .count();
+    let from = &from_words[..from_words.len() - common];
+    let to = &to_words[..to_words.len() - common];
+
+    let single_words = from.len() == 1 && to.len() == 1;
+    let added = to.starts_with(from).then(|| &to[from.len()..]);
+    if added.is_some_and(|added| added.iter().any(|word| !word.starts_with('-'))) {
+        return None;
+    }
+    let appends_flags = added.is_some_and(|added| !added.is_empty());
+    if common == 0 && !single_words && !appends_flags {
+        return None;
+    }
+
+    let (from, to) = (from.join(" "), to.join(" "));
+    (from != to).then_some((from, to))
+}
+
+/// Returns the raw words of a simple command from the command word onwards.
+fn raw_words<'a>(line: &'a str, command: &SimpleCommand) -> Vec<&'a str> {
+    command.words[command.command_index..]
+        .iter()
+        .map(|word| &line[word.start..word.end])
+        .collect()
+}
+
+/// Returns true if an existing rule already handles the command.
+fn is_mapped(config: &Config, command: &str) -> bool {
+    matches!(check_command_mappings(config, command, None), Ok(Some(_)))
+}
+
+/// Parses `all` or a comma-separated list of suggestion numbers.
+fn select_candidates<'a>(candidates: &'a [MappingCandidate], apply: &str) -> Result<Vec<&'a MappingCandidate>> {
+    if apply.trim() == "all" {
+        return Ok(candidates.iter().collect());
+    }
+
+    let mut chosen = Vec::new();
+    for number in apply.split(',').map(str::trim).filter(|number| !number.is_empty()) {
+        let index: usize = number
+            .parse()
+            .with_context(|| format!("Invalid suggestion number '{number}'"))?;
+        let Some(candidate) = index.checked_sub(1).and_then(|index| candidates.get(index)) else {
+            bail!("There is no suggestion number {index}");
+        };
+        chosen.push(candidate);
+    }
+
+    Ok(chosen)
+}
+
+/// Appends the mappings as `[[command_rules]]` tables, keeping the rest of
+/// the file (including comments) untouched.
+fn append_mappings(path: &Path, candidates: &[&MappingCandidate]) -> Result<()> {
+    let mut content = if path.exists() {
+        fs::read_to_string(path).with_context(|| format!("Failed to read config file: {}", path.display()))?
+    } else {
+        String::new()
+    };
+
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+
+    for candidate in candidates {
+        let rule = CommandRule {
+            id: None,
+            pattern: candidate.from.clone(),
+            replacement: candidate.to.clone(),
+            priority: 0,
+            when: None,
+            action: RuleAction::Block,
+        };
+        content.push_str(&format!(
+            "\n# Suggested from command history (seen {} time(s))\n[[command_rules]]\n{}",
+            candidate.count,
+            toml::to_string(&rule)?
+        ));
+    }
+
+    fs::write(path, content).with_context(|| format!("Failed to write config file: {}", path.display()))
+}
+
+/// Returns the project config file that suggestions are appended to.
+fn project_config_path(config_path: &str) -> PathBuf {
+    discover_config_layers(config_path, None)
+        .into_iter()
+        .find(|layer| layer.kind == "project")
+        .map_or_else(|| PathBuf::from(config_path), |layer| layer.path)
+}
+
+/// Returns the history database path if history is enabled.
+fn history_database(config: &Config) -> Result<Option<PathBuf>> {
+    match config.command_history.as_ref().filter(|cfg| cfg.enabled) {
+        Some(history_config) => Ok(Some(expand_tilde(&history_config.log_file)?)),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(session: &str, command: &str, status: &str) -> CommandRecord {
+        let mut record = history::create_record(session, command, None, Some("/project"), false, None, status);
+        if status == "success" {
+            record.exit_code = Some(0);
+        }
+        record
+    }
+
+    #[test]
+    fn test_mapping_between_commands() {
+        let cases = [
+            ("npm install", "bun install", Some(("npm", "bun"))),
+            ("npm run build", "bun run build", Some(("npm", "bun"))),
+            ("python script.py", "python3 script.py", Some(("python", "python3"))),
+            ("pip install requests", "uv pip install requests", Some(("pip", "uv pip"))),
+            ("ls", "eza", Some(("ls", "eza"))),
+            ("pip install x", "x", None),
+            ("git commit", "git commit --no-verify", Some(("git commit", "git commit --no-verify"))),
+            // `git push origin feature` would become `git push -u origin HEAD origin feature`
+            ("git push", "git push -u origin HEAD", None),
+            ("cd web && npm test", "cd web && bun test", Some(("npm", "bun"))),
+            ("cargo test foo", "cargo test bar", None),
+            ("npm install", "npm install", None),
+            ("cd a && npm test", "cd b && bun test", None),
+            ("npm test", "npm test && echo ok", None),
+        ];
+
+        for (failed, succeeded, expected) in cases {
+            let expected = expected.map(|(from, to)| (from.to_string(), to.to_string()));
+            assert_eq!(mapping_between(failed, succeeded), expected, "{failed} → {succeeded}");
+        }
+    }
+
+    #[test]
+    fn test_mine_candidates_ranks_by_frequency() {
+        let records = vec![
+            record("s1", "npm install", "pending"),
+            record("s1", "bun install", "success"),
+            record("s1", "python x.py", "pending"),
+            record("s2", "npm test", "pending"),
+            // Interleaved sessions are tracked separately
+            record("s1", "python3 x.py", "success"),
+            record("s2", "bun test", "success"),
+            record("s2", "npm ci", "pending"),
+            record("s2", "bun ci", "pending"),
+            record("s3", "python y.py", "pending"),
+            record("s3", "python3 y.py", "success"),
+        ];
+
+        let candidates = mine_candidates(&records);
+        let summary: Vec<(&str, &str, usize, usize)> = candidates
+            .iter()
+            .map(|candidate| (candidate.from.as_str(), candidate.to.as_str(), candidate.count, candidate.sessions))
+            .collect();
+
+        assert_eq!(summary, vec![("npm", "bun", 2, 2), ("python", "python3", 2, 2)]);
+        assert_eq!(candidates[0].example, ("npm install".to_string(), "bun install".to_string()));
+    }
+
+    #[test]
+    fn test_already_mapped_commands_are_skipped() {
+        let config: Config = toml::from_str("[commands]\nnpm = \"bun\"\n").unwrap();
+        assert!(is_mapped(&config, "npm"));
+        assert!(!is_mapped(&config, "python"));
+    }
+
+    #[test]
+    fn test_select_and_append_mappings() {
+        let candidate = |from: &str, to: &str| MappingCandidate {
+            from: from.to_string(),
+            to: to.to_string(),
+            count: 3,
+            sessions: 2,
+            example: (from.to_string(), to.to_string()),
+        };
+        let candidates = vec![candidate("npm", "bun"), candidate("python", "python3")];
+
+        assert_eq!(select_candidates(&candidates, "all").unwrap().len(), 2);
+        assert_eq!(select_candidates(&candidates, "2").unwrap()[0].from, "python");
+        assert!(select_candidates(&candidates, "3").is_err());
+        assert!(select_candidates(&candidates, "x").is_err());
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(".claude-hook-advisor.toml");
+        fs::write(&path, "# Team config\n[commands]\nyarn = \"bun\"").unwrap();
+        append_mappings(&path, &select_candidates(&candidates, "1, 2").unwrap()).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("# Team config\n"));
+        let config: Config = toml::from_str(&content).unwrap();
+        assert_eq!(config.commands["yarn"], "bun");
+        let rules: Vec<(&str, &str)> = config
+            .command_rules
+            .iter()
+            .map(|rule| (rule.pattern.as_str(), rule.replacement.as_str()))
+            .collect();
+        assert_eq!(rules, vec![("npm", "bun"), ("python", "python3")]);
+    }
+}
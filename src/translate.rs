@@ -0,0 +1,588 @@
+//! Flag translation profiles for tool replacements
+//!
+//! A mapping such as `grep = "rg"` only swaps the binary name, but the two
+//! tools do not share flags (`grep -r` is recursive, `rg -r` is `--replace`).
+//! Profiles translate arguments between common tool pairs so the suggested
+//! command means the same thing. Arguments a profile cannot translate
+//! faithfully make the translation unsupported rather than guessing.
+
+/// Result of translating a command's arguments.
+#[derive(Debug, PartialEq)]
+pub enum Translation {
+    /// No profile exists for this tool pair
+    NoProfile,
+    /// Full translated argv, starting with the replacement words
+    Translated(Vec<String>),
+    /// A profile exists but the arguments cannot be translated faithfully
+    Unsupported,
+}
+
+/// A translation profile between two tools.
+struct Profile {
+    from: &'static str,
+    to: &'static str,
+    /// Subcommand the target needs right after the binary (e.g. `uv pip`)
+    subcommand: Option<&'static str>,
+    translate: fn(&[String]) -> Option<Vec<String>>,
+}
+
+/// Built-in translation profiles.
+const PROFILES: &[Profile] = &[
+    Profile { from: "grep", to: "rg", subcommand: None, translate: grep_to_rg },
+    Profile { from: "find", to: "fd", subcommand: None, translate: find_to_fd },
+    Profile { from: "ls", to: "eza", subcommand: None, translate: ls_to_eza },
+    Profile { from: "cat", to: "bat", subcommand: None, translate: cat_to_bat },
+    Profile { from: "npm", to: "bun", subcommand: None, translate: npm_to_bun },
+    Profile { from: "pip", to: "uv", subcommand: Some("pip"), translate: pip_to_uv },
+];
+
+/// Translates the arguments of a mapped command.
+///
+/// Profiles only apply to single-word patterns (a binary swap) whose
+/// replacement starts with the profile's target binary. Extra words in the
+/// replacement (e.g. `rg --hidden`) are kept in front of the translated
+/// arguments.
+///
+/// # Arguments
+/// * `pattern` - The rule pattern (source binary)
+/// * `replacement` - The rule replacement
+/// * `args` - Unquoted arguments following the source binary
+pub fn translate(pattern: &str, replacement: &str, args: &[String]) -> Translation {
+    let replacement_words: Vec<&str> = replacement.split_whitespace().collect();
+    let Some(&target) = replacement_words.first() else {
+        return Translation::NoProfile;
+    };
+
+    let Some(profile) = PROFILES
+        .iter()
+        .find(|profile| profile.from == pattern.trim() && profile.to == target)
+    else {
+        return Translation::NoProfile;
+    };
+
+    let Some(translated) = (profile.translate)(args) else {
+        return Translation::Unsupported;
+    };
+
+    let mut argv: Vec<String> = replacement_words.iter().map(|word| word.to_string()).collect();
+    if let Some(subcommand) = profile.subcommand {
+        if replacement_words.get(1) != Some(&subcommand) {
+            argv.insert(1, subcommand.to_string());
+        }
+    }
+    // Long flags the replacement already passes (e.g. `rg --hidden`) are not repeated
+    argv.extend(
+        translated
+            .into_iter()
+            .filter(|arg| !(arg.starts_with("--") && arg != "--" && replacement_words.contains(&arg.as_str()))),
+    );
+    Translation::Translated(argv)
+}
+
+/// Splits combined short flags (`-rnA3` becomes `-r`, `-n`, `-A3`).
+///
+/// Splitting stops at the first flag in `takes_value`, which keeps the rest
+/// of the word as its value.
+fn split_short_flags(arg: &str, takes_value: &str) -> Vec<String> {
+    let Some(flags) = arg.strip_prefix('-').filter(|rest| !rest.is_empty() && !rest.starts_with('-')) else {
+        return vec![arg.to_string()];
+    };
+
+    let mut result = Vec::new();
+    for (i, flag) in flags.char_indices() {
+        if takes_value.contains(flag) {
+            result.push(format!("-{}", &flags[i..]));
+            return result;
+        }
+        result.push(format!("-{flag}"));
+    }
+    result
+}
+
+/// grep → rg
+///
+/// grep defaults to basic regular expressions, where `|`, `+`, `?`, `(`,
+/// `)`, `{` and `}` are literal unless escaped; rg reads them the other way
+/// round. Without `-E`, `-F` or `-P`, patterns using any of them are
+/// unsupported, as are patterns read from a file. GNU word anchors (`\<`,
+/// `\>`) are unsupported unless the pattern is fixed or Perl syntax. A
+/// recursive search gets `--hidden --no-ignore`, since rg otherwise skips
+/// hidden and ignored files that `grep -r` searches.
+fn grep_to_rg(args: &[String]) -> Option<Vec<String>> {
+    const TAKES_VALUE: &str = "ABCefmdD";
+    const COMPATIBLE: &str = "ivwxclnoqHFPabmABCef";
+
+    let mut result = Vec::new();
+    let mut iter = args.iter();
+    let mut patterns: Vec<String> = Vec::new();
+    let mut positional: Option<String> = None;
+    let mut pattern_file = false;
+    let mut extended = false;
+    let mut fixed_or_perl = false;
+    let mut recursive = false;
+
+    while let Some(arg) = iter.next() {
+        if arg == "--" {
+            result.push(arg.clone());
+            positional = positional.or_else(|| iter.clone().next().cloned());
+            result.extend(iter.by_ref().cloned());
+            break;
+        }
+
+        if let Some(long) = arg.strip_prefix("--") {
+            let (name, value) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (long, None),
+            };
+            let value_or_next = |iter: &mut std::slice::Iter<String>| value.clone().or_else(|| iter.next().cloned());
+
+            match name {
+                "recursive" | "dereference-recursive" => recursive = true,
+                "extended-regexp" => extended = true,
+                "fixed-strings" | "perl-regexp" => {
+                    fixed_or_perl = true;
+                    result.push(arg.clone());
+                }
+                "regexp" | "file" => {
+                    let value = value_or_next(&mut iter)?;
+                    if name == "file" {
+                        pattern_file = true;
+                    } else {
+                        patterns.push(value.clone());
+                    }
+                    result.push(format!("--{name}={value}"));
+                }
+                "include" => result.push(format!("--glob={}", value_or_next(&mut iter)?)),
+                "exclude" | "exclude-dir" => result.push(format!("--glob=!{}", value_or_next(&mut iter)?)),
+                "basic-regexp" | "null-data" => return None,
+                _ => result.push(arg.clone()),
+            }
+            continue;
+        }
+
+        if !arg.starts_with('-') || arg == "-" {
+            positional = positional.or_else(|| Some(arg.clone()));
+            result.push(arg.clone());
+            continue;
+        }
+
+        for flag in split_short_flags(arg, TAKES_VALUE) {
+            let letter = flag.chars().nth(1)?;
+            match letter {
+                'r' | 'R' => recursive = true,
+                'I' => {}
+                'E' => extended = true,
+                'h' => result.push("--no-filename".to_string()),
+                'L' => result.push("--files-without-match".to_string()),
+                's' => result.push("--no-messages".to_string()),
+                'Z' => result.push("--null".to_string()),
+                'y' => result.push("-i".to_string()),
+                _ if COMPATIBLE.contains(letter) => {
+                    let needs_value = TAKES_VALUE.contains(letter) && flag.len() == 2;
+                    let attached = flag[2..].to_string();
+                    result.push(flag);
+                    let value = if needs_value {
+                        let value = iter.next()?.clone();
+                        result.push(value.clone());
+                        value
+                    } else {
+                        attached
+                    };
+                    match letter {
+                        'F' | 'P' => fixed_or_perl = true,
+                        'e' => patterns.push(value),
+                        'f' => pattern_file = true,
+                        _ => {}
+                    }
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    if !fixed_or_perl {
+        if pattern_file {
+            return None;
+        }
+        // With -e or -f every positional argument is a path
+        if patterns.is_empty() {
+            patterns.extend(positional);
+        }
+        if patterns.iter().any(|pattern| pattern.contains("\\<") || pattern.contains("\\>")) {
+            return None;
+        }
+        if !extended && patterns.iter().any(|pattern| pattern.contains(['|', '+', '?', '(', ')', '{', '}'])) {
+            return None;
+        }
+    }
+
+    if recursive {
+        result.splice(0..0, ["--hidden".to_string(), "--no-ignore".to_string()]);
+    }
+    Some(result)
+}
+
+/// find → fd
+///
+/// fd skips hidden and ignored files by default, so `--hidden --no-ignore`
+/// keeps the search as wide as find's.
+fn find_to_fd(args: &[String]) -> Option<Vec<String>> {
+    let mut paths = Vec::new();
+    let mut flags = vec!["--hidden".to_string(), "--no-ignore".to_string()];
+    let mut glob: Option<String> = None;
+    let mut iter = args.iter().peekable();
+
+    // Leading options and starting points
+    while let Some(arg) = iter.peek() {
+        match arg.as_str() {
+            "-L" | "-follow" => flags.push("--follow".to_string()),
+            "-H" | "-P" => {}
+            _ if arg.starts_with('-') || *arg == "(" || *arg == "!" => break,
+            _ => paths.push(arg.to_string()),
+        }
+        iter.next();
+    }
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-name" | "-iname" => {
+                if glob.is_some() {
+                    return None;
+                }
+                glob = Some(iter.next()?.clone());
+                if arg == "-iname" {
+                    flags.push("--ignore-case".to_string());
+                }
+            }
+            "-type" => {
+                let kind = iter.next()?;
+                if !matches!(kind.as_str(), "f" | "d" | "l" | "s" | "p") {
+                    return None;
+                }
+                flags.push("--type".to_string());
+                flags.push(kind.clone());
+            }
+            "-maxdepth" => {
+                flags.push("--max-depth".to_string());
+                flags.push(iter.next()?.clone());
+            }
+            "-mindepth" => {
+                flags.push("--min-depth".to_string());
+                flags.push(iter.next()?.clone());
+            }
+            "-empty" => flags.extend(["--type".to_string(), "empty".to_string()]),
+            "-executable" => flags.extend(["--type".to_string(), "executable".to_string()]),
+            "-follow" => flags.push("--follow".to_string()),
+            "-print" => {}
+            "-print0" => flags.push("--print0".to_string()),
+            "-exec" => {
+                let mut command = Vec::new();
+                let batch = loop {
+                    let word = iter.next()?;
+                    match word.as_str() {
+                        ";" => break false,
+                        "+" => break true,
+                        _ => command.push(word.clone()),
+                    }
+                };
+                flags.push(if batch { "--exec-batch" } else { "--exec" }.to_string());
+                flags.extend(command);
+                // fd's exec options consume arguments up to the next `;`
+                flags.push(";".to_string());
+            }
+            // Boolean operators, time/size predicates, -delete, -path etc.
+            _ => return None,
+        }
+    }
+
+    let mut result = flags;
+    if paths.iter().all(|path| path == ".") {
+        paths.clear();
+    }
+
+    match glob {
+        Some(glob) => {
+            result.push("--glob".to_string());
+            result.push(glob);
+        }
+        None if !paths.is_empty() => result.push(".".to_string()),
+        None => {}
+    }
+
+    result.extend(paths);
+    Some(result)
+}
+
+/// ls → eza
+fn ls_to_eza(args: &[String]) -> Option<Vec<String>> {
+    const COMPATIBLE: &str = "l1RdFinra";
+
+    let mut result = Vec::new();
+    let mut sort: Option<&str> = None;
+    let mut reverse = false;
+
+    for arg in args {
+        if arg.starts_with("--") || !arg.starts_with('-') || arg == "-" {
+            result.push(arg.clone());
+            continue;
+        }
+
+        for flag in split_short_flags(arg, "") {
+            match flag.chars().nth(1)? {
+                'h' | 'G' => {}
+                'A' => result.push("-a".to_string()),
+                's' => result.push("--blocksize".to_string()),
+                't' => sort = Some("modified"),
+                'S' => sort = Some("size"),
+                'r' => reverse = true,
+                letter if COMPATIBLE.contains(letter) => result.push(flag),
+                _ => return None,
+            }
+        }
+    }
+
+    // ls sorts time and size descending; eza sorts ascending
+    if let Some(field) = sort {
+        result.insert(0, format!("--sort={field}"));
+        reverse = !reverse;
+    }
+    if reverse {
+        result.insert(0, "--reverse".to_string());
+    }
+
+    Some(result)
+}
+
+/// cat → bat
+fn cat_to_bat(args: &[String]) -> Option<Vec<String>> {
+    let mut result = Vec::new();
+    let mut numbered = false;
+
+    for arg in args {
+        if arg.starts_with("--") || !arg.starts_with('-') || arg == "-" {
+            result.push(arg.clone());
+            continue;
+        }
+
+        for flag in split_short_flags(arg, "") {
+            match flag.chars().nth(1)? {
+                'n' | 'b' => numbered = true,
+                'A' | 'e' | 'E' | 'T' | 't' | 'v' => result.push("--show-all".to_string()),
+                's' => result.push("--squeeze-blank".to_string()),
+                'u' => {}
+                _ => return None,
+            }
+        }
+    }
+
+    // bat only decorates output on a terminal; force line numbers when asked
+    if numbered {
+        result.insert(0, "--decorations=always".to_string());
+        result.insert(0, "--style=numbers".to_string());
+    }
+    result.insert(0, "--paging=never".to_string());
+
+    Some(result)
+}
+
+/// npm → bun
+fn npm_to_bun(args: &[String]) -> Option<Vec<String>> {
+    let Some((subcommand, rest)) = args.split_first() else {
+        return Some(Vec::new());
+    };
+
+    let mut flags = Vec::new();
+    let mut positional = Vec::new();
+    for arg in rest {
+        match arg.as_str() {
+            "--save" | "-S" => {}
+            "--save-dev" | "-D" => flags.push("--dev".to_string()),
+            "--save-optional" | "-O" => flags.push("--optional".to_string()),
+            "--save-exact" | "-E" => flags.push("--exact".to_string()),
+            "--save-peer" => flags.push("--peer".to_string()),
+            _ if arg.starts_with('-') => flags.push(arg.clone()),
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    let mut result = Vec::new();
+    match subcommand.as_str() {
+        "install" | "i" | "add" | "isntall" => {
+            result.push(if positional.is_empty() { "install" } else { "add" }.to_string());
+            result.extend(positional);
+            result.extend(flags);
+        }
+        "ci" => {
+            result.extend(["install".to_string(), "--frozen-lockfile".to_string()]);
+            result.extend(flags);
+        }
+        "uninstall" | "un" | "remove" | "rm" | "r" => {
+            result.push("remove".to_string());
+            result.extend(positional);
+            result.extend(flags);
+        }
+        // Scripts: everything after the script name belongs to the script
+        "run" | "run-script" => {
+            result.push("run".to_string());
+            result.extend(rest.iter().cloned());
+        }
+        "test" | "t" | "tst" | "start" | "stop" | "restart" => {
+            let script = match subcommand.as_str() {
+                "t" | "tst" => "test",
+                other => other,
+            };
+            result.extend(["run".to_string(), script.to_string()]);
+            result.extend(rest.iter().cloned());
+        }
+        "update" | "up" | "upgrade" => {
+            result.push("update".to_string());
+            result.extend(positional);
+            result.extend(flags);
+        }
+        "exec" | "x" => {
+            result.push("x".to_string());
+            result.extend(rest.iter().filter(|arg| *arg != "--").cloned());
+        }
+        "init" | "link" | "unlink" | "outdated" | "publish" => {
+            result.push(subcommand.clone());
+            result.extend(rest.iter().cloned());
+        }
+        _ => return None,
+    }
+
+    Some(result)
+}
+
+/// pip → uv pip
+fn pip_to_uv(args: &[String]) -> Option<Vec<String>> {
+    let subcommand = args.first()?;
+    if !matches!(
+        subcommand.as_str(),
+        "install" | "uninstall" | "freeze" | "list" | "show" | "check" | "tree"
+    ) {
+        return None;
+    }
+    // uv pip has no user site installs
+    if args.iter().any(|arg| arg == "--user") {
+        return None;
+    }
+
+    let translated = args
+        .iter()
+        .filter_map(|arg| match arg.as_str() {
+            "-y" | "--yes" | "--disable-pip-version-check" => None,
+            "--no-cache-dir" => Some("--no-cache".to_string()),
+            _ => Some(arg.clone()),
+        })
+        .collect();
+
+    Some(translated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(pattern: &str, replacement: &str, args: &[&str]) -> Translation {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        translate(pattern, replacement, &args)
+    }
+
+    fn translated(pattern: &str, replacement: &str, args: &[&str]) -> String {
+        match run(pattern, replacement, args) {
+            Translation::Translated(argv) => argv.join(" "),
+            other => panic!("expected translation, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_grep_to_rg() {
+        // Recursive searches include the hidden and ignored files grep sees
+        assert_eq!(translated("grep", "rg", &["-rn", "foo", "."]), "rg --hidden --no-ignore -n foo .");
+        assert_eq!(translated("grep", "rg", &["-rhiA3", "foo"]), "rg --hidden --no-ignore --no-filename -i -A3 foo");
+        assert_eq!(translated("grep", "rg --hidden", &["-r", "foo", "node_modules"]), "rg --hidden --no-ignore foo node_modules");
+        assert_eq!(translated("grep", "rg", &["-B", "2", "--include=*.rs", "x"]), "rg -B 2 --glob=*.rs x");
+        assert_eq!(translated("grep", "rg --hidden", &["-E", "a|b"]), "rg --hidden a|b");
+        assert_eq!(run("grep", "rg", &["--basic-regexp", "x"]), Translation::Unsupported);
+        // GNU word anchors
+        assert_eq!(run("grep", "rg", &["\\<main\\>", "src"]), Translation::Unsupported);
+        assert_eq!(run("grep", "rg", &["-E", "\\<(a|b)\\>"]), Translation::Unsupported);
+        assert_eq!(translated("grep", "rg", &["-F", "\\<br\\>", "x.html"]), "rg -F \\<br\\> x.html");
+    }
+
+    #[test]
+    fn test_grep_to_rg_basic_regex() {
+        // BRE alternation and literal parentheses change meaning under rg
+        assert_eq!(run("grep", "rg", &["a\\|b", "src"]), Translation::Unsupported);
+        assert_eq!(run("grep", "rg", &["-rn", "foo(", "."]), Translation::Unsupported);
+        assert_eq!(run("grep", "rg", &["-e", "x+", "-e", "y"]), Translation::Unsupported);
+        assert_eq!(run("grep", "rg", &["-f", "patterns.txt", "."]), Translation::Unsupported);
+        // Syntax flags make the pattern unambiguous
+        assert_eq!(translated("grep", "rg", &["-F", "foo(", "."]), "rg -F foo( .");
+        assert_eq!(translated("grep", "rg", &["-E", "a|b"]), "rg a|b");
+        // Metacharacters in paths after an explicit pattern are fine
+        assert_eq!(translated("grep", "rg", &["-e", "foo", "a(1).txt"]), "rg -e foo a(1).txt");
+    }
+
+    #[test]
+    fn test_find_to_fd() {
+        // Hidden and ignored files are found, as with find
+        assert_eq!(translated("find", "fd", &[".", "-name", "*.env"]), "fd --hidden --no-ignore --glob *.env");
+        assert_eq!(
+            translated("find", "fd", &["src", "-type", "f", "-maxdepth", "2", "-iname", "*.MD"]),
+            "fd --hidden --no-ignore --type f --max-depth 2 --ignore-case --glob *.MD src"
+        );
+        assert_eq!(translated("find", "fd", &["src", "-type", "d"]), "fd --hidden --no-ignore --type d . src");
+        assert_eq!(
+            translated("find", "fd", &[".", "-name", "*.tmp", "-exec", "rm", "{}", ";"]),
+            "fd --hidden --no-ignore --exec rm {} ; --glob *.tmp"
+        );
+        assert_eq!(translated("find", "fd --hidden", &["src"]), "fd --hidden --no-ignore . src");
+        assert_eq!(run("find", "fd", &[".", "-mtime", "-1"]), Translation::Unsupported);
+        assert_eq!(run("find", "fd", &[".", "-name", "a", "-o", "-name", "b"]), Translation::Unsupported);
+    }
+
+    #[test]
+    fn test_ls_to_eza() {
+        assert_eq!(translated("ls", "eza", &["-lah"]), "eza -l -a");
+        assert_eq!(translated("ls", "eza", &["-lt"]), "eza --reverse --sort=modified -l");
+        assert_eq!(translated("ls", "eza", &["-ltr", "src"]), "eza --sort=modified -l src");
+        assert_eq!(run("ls", "eza", &["-u"]), Translation::Unsupported);
+    }
+
+    #[test]
+    fn test_cat_to_bat() {
+        assert_eq!(translated("cat", "bat", &["file.rs"]), "bat --paging=never file.rs");
+        assert_eq!(
+            translated("cat", "bat", &["-n", "file.rs"]),
+            "bat --paging=never --style=numbers --decorations=always file.rs"
+        );
+    }
+
+    #[test]
+    fn test_npm_to_bun() {
+        assert_eq!(translated("npm", "bun", &["install"]), "bun install");
+        assert_eq!(translated("npm", "bun", &["i", "-D", "vitest"]), "bun add vitest --dev");
+        assert_eq!(translated("npm", "bun", &["ci"]), "bun install --frozen-lockfile");
+        assert_eq!(translated("npm", "bun", &["test"]), "bun run test");
+        assert_eq!(translated("npm", "bun", &["run", "build", "--", "--prod"]), "bun run build -- --prod");
+        assert_eq!(translated("npm", "bun", &["uninstall", "left-pad"]), "bun remove left-pad");
+        assert_eq!(run("npm", "bun", &["audit"]), Translation::Unsupported);
+    }
+
+    #[test]
+    fn test_pip_to_uv() {
+        assert_eq!(translated("pip", "uv", &["install", "--no-cache-dir", "requests"]), "uv pip install --no-cache requests");
+        // uv has no user site installs
+        assert_eq!(run("pip", "uv", &["install", "--user", "requests"]), Translation::Unsupported);
+        assert_eq!(translated("pip", "uv pip", &["uninstall", "-y", "requests"]), "uv pip uninstall requests");
+        assert_eq!(run("pip", "uv", &["download", "requests"]), Translation::Unsupported);
+    }
+
+    #[test]
+    fn test_no_profile() {
+        assert_eq!(run("curl", "wget", &["-L", "x"]), Translation::NoProfile);
+        assert_eq!(run("npm test", "bun test", &[]), Translation::NoProfile);
+        assert_eq!(run("grep", "ag", &["-r", "x"]), Translation::NoProfile);
+    }
+}
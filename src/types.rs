@@ -1,22 +1,340 @@
 //! Type definitions for Claude Hook Advisor
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Configuration structure for command mappings and directory aliasing.
 ///
-/// Loaded from .claude-hook-advisor.toml files, this struct contains
+/// Loaded from .claude-hook-advisor.toml files (merged across the global,
+/// project and local layers), this struct contains
 /// the mapping from original commands to their preferred replacements
 /// and semantic directory aliases for natural language references.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct Config {
+    #[serde(default)]
     pub commands: HashMap<String, String>,
     #[serde(default)]
     pub semantic_directories: HashMap<String, String>,
     #[serde(default)]
     pub command_history: Option<CommandHistoryConfig>,
     #[serde(default)]
-    pub security_pattern_overrides: HashMap<String, bool>,
+    pub security_pattern_overrides: HashMap<String, PatternOverride>,
+    /// What a `critical` security finding does: `deny` (default) or `ask`
+    #[serde(default, skip_serializing_if = "CriticalAction::is_default")]
+    pub security_critical_action: CriticalAction,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub security_patterns: Vec<SecurityPattern>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub dangerous_command_overrides: HashMap<String, bool>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub interactive_command_overrides: HashMap<String, InteractiveAction>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub secret_overrides: HashMap<String, SecretAction>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub interactive_commands: Vec<InteractiveCommand>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env_rules: Vec<EnvRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub command_rules: Vec<CommandRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rewrite_rules: Vec<RewriteRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tests: Vec<RuleTest>,
+}
+
+/// Command mapping rule in `[[command_rules]]` table form.
+///
+/// Plain `[commands]` entries are treated as rules with priority 0. Rules
+/// with a higher priority are checked first; among equal priorities the most
+/// specific (longest) pattern wins.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CommandRule {
+    /// Identifier used in messages; defaults to the pattern
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub pattern: String,
+    pub replacement: String,
+    #[serde(default)]
+    pub priority: i32,
+    /// Conditions that must hold for the rule to apply
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<RuleCondition>,
+    /// What happens when the rule matches
+    #[serde(default, skip_serializing_if = "RuleAction::is_default")]
+    pub action: RuleAction,
+}
+
+impl CommandRule {
+    /// Returns the explicit rule id, or the pattern if none was given.
+    pub fn id(&self) -> &str {
+        self.id.as_deref().unwrap_or(&self.pattern)
+    }
+}
+
+/// Regex rewrite rule in `[[rewrite_rules]]` table form.
+///
+/// `match` is a regex applied to each simple command (starting at the command
+/// word); `replace` may reference capture groups as `$1`, `$2` or `${name}`.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RewriteRule {
+    /// Identifier used in messages; defaults to the pattern
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "match")]
+    pub pattern: String,
+    pub replace: String,
+    #[serde(default)]
+    pub priority: i32,
+    /// Conditions that must hold for the rule to apply
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<RuleCondition>,
+    /// What happens when the rule matches
+    #[serde(default, skip_serializing_if = "RuleAction::is_default")]
+    pub action: RuleAction,
+}
+
+impl RewriteRule {
+    /// Returns the explicit rule id, or the pattern if none was given.
+    pub fn id(&self) -> &str {
+        self.id.as_deref().unwrap_or(&self.pattern)
+    }
+}
+
+/// What the hook does when a rule matches a Bash command.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleAction {
+    /// Let the command run and tell Claude about the suggested command
+    Suggest,
+    /// Run the suggested command instead, without blocking
+    Rewrite,
+    /// Ask the user to confirm the original command
+    Ask,
+    /// Block the command and suggest the replacement (the default)
+    #[default]
+    Block,
+    /// Block the command outright, without a replacement
+    Deny,
+}
+
+impl RuleAction {
+    /// Returns the name used in configuration files.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleAction::Suggest => "suggest",
+            RuleAction::Rewrite => "rewrite",
+            RuleAction::Ask => "ask",
+            RuleAction::Block => "block",
+            RuleAction::Deny => "deny",
+        }
+    }
+
+    fn is_default(&self) -> bool {
+        *self == RuleAction::Block
+    }
+}
+
+/// Conditions for a rule, given as `when = { ... }`.
+///
+/// Conditions are evaluated against the hook's working directory. Every
+/// condition that is set must hold for the rule to apply.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+pub struct RuleCondition {
+    /// File that must exist in the working directory or one of its parents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_exists: Option<String>,
+    /// Executable that must be found on PATH
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_path: Option<String>,
+    /// Glob the working directory must match (supports `*` and `**`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd_glob: Option<String>,
+}
+
+/// What the hook does with a command that needs a terminal or never exits.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum InteractiveAction {
+    /// Block the command and explain how to run it non-interactively
+    #[default]
+    Deny,
+    /// Run the non-interactive form instead (falls back to `deny` if there is none)
+    Rewrite,
+    /// Block the command and suggest running it in the background
+    Background,
+    /// Do not check for this command
+    Off,
+}
+
+impl InteractiveAction {
+    /// Returns the name used in configuration files.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InteractiveAction::Deny => "deny",
+            InteractiveAction::Rewrite => "rewrite",
+            InteractiveAction::Background => "background",
+            InteractiveAction::Off => "off",
+        }
+    }
+}
+
+/// What the hook does with a Bash command that contains a secret.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretAction {
+    /// Block the command and explain how to pass the secret safely
+    Block,
+    /// Ask the user to confirm the command
+    Ask,
+    /// Do not check for (or redact) this kind of secret
+    Off,
+}
+
+impl SecretAction {
+    /// Returns the name used in configuration files.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SecretAction::Block => "block",
+            SecretAction::Ask => "ask",
+            SecretAction::Off => "off",
+        }
+    }
+}
+
+/// How serious a security pattern match is, which decides the hook response.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Allow the edit and pass the reminder to Claude as context
+    Info,
+    /// Block the edit once per file and session with the reminder (the default)
+    #[default]
+    Warn,
+    /// Deny the edit (or ask the user) every time
+    Critical,
+}
+
+impl Severity {
+    /// Returns the name used in configuration files.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warn => "warn",
+            Severity::Critical => "critical",
+        }
+    }
+
+    fn is_default(&self) -> bool {
+        *self == Severity::Warn
+    }
+}
+
+/// What the hook does with an edit that has a `critical` security finding.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CriticalAction {
+    /// Deny the edit (the default)
+    #[default]
+    Deny,
+    /// Ask the user to confirm the edit
+    Ask,
+}
+
+impl CriticalAction {
+    fn is_default(&self) -> bool {
+        *self == CriticalAction::Deny
+    }
+}
+
+/// Entry in `[security_pattern_overrides]`: `false` disables a pattern,
+/// `true` keeps it, and a severity name changes how seriously it is taken.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum PatternOverride {
+    Enabled(bool),
+    Severity(Severity),
+}
+
+/// Project-specific interactive command in `[[interactive_commands]]` table form.
+///
+/// `pattern` is a literal command prefix, matched like `[commands]` keys.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct InteractiveCommand {
+    /// Identifier used in messages; defaults to the pattern
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub pattern: String,
+    /// Explanation shown when the command is blocked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub action: InteractiveAction,
+    /// Non-interactive replacement for the pattern, used by `rewrite`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<String>,
+}
+
+impl InteractiveCommand {
+    /// Returns the explicit id, or the pattern if none was given.
+    pub fn id(&self) -> &str {
+        self.id.as_deref().unwrap_or(&self.pattern)
+    }
+}
+
+/// Environment variables for matching commands in `[[env_rules]]` table form.
+///
+/// `pattern` is a literal command prefix, matched like `[commands]` keys. The
+/// variables in `env` are prefixed to the command (`CI=true npm test`) unless
+/// the command already sets them.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct EnvRule {
+    /// Identifier used in messages; defaults to the pattern
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub pattern: String,
+    pub env: BTreeMap<String, String>,
+    /// Conditions that must hold for the rule to apply
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<RuleCondition>,
+}
+
+impl EnvRule {
+    /// Returns the explicit rule id, or the pattern if none was given.
+    pub fn id(&self) -> &str {
+        self.id.as_deref().unwrap_or(&self.pattern)
+    }
+}
+
+/// Expected hook behaviour in `[[tests]]` table form.
+///
+/// A test gives either a Bash `command` or a file edit (`file` plus optional
+/// `content`) and the decision the hook should make for it. Tests are run by
+/// the `test-rules` subcommand.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct RuleTest {
+    /// Name shown in test output; defaults to the command or file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Bash command to evaluate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Working directory the command runs in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Path of the file being written or edited
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// Content being written to `file`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub content: String,
+    /// Expected decision: allow, suggest, rewrite, ask, block or deny
+    pub expect: String,
+    /// Expected replacement command
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rewrite: Option<String>,
+    /// Rule id, policy detector or security pattern expected to match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
 }
 
 /// Configuration for command history tracking
@@ -30,15 +348,37 @@ pub struct CommandHistoryConfig {
 
 /// Security pattern for detecting risky code patterns in file edits.
 ///
-/// Patterns can match based on file path patterns (glob-style) or content substrings.
-/// When a match is found, the specified reminder is shown to Claude.
-#[derive(Debug, Deserialize, Serialize, Clone)]
+/// Patterns can match based on file path patterns (glob-style) or content
+/// (substrings and regexes), optionally limited to files with given
+/// extensions or languages. Content matches on lines that match a
+/// `not_content` regex are ignored, and the pattern only fires once it has
+/// at least `min_matches` content matches. When a match is found, the
+/// specified reminder is shown to Claude in the way its `severity` asks for.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
 pub struct SecurityPattern {
     pub rule_name: String,
     #[serde(default)]
     pub path_pattern: Option<String>,
+    /// File extensions (without the dot) the pattern is limited to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub file_extensions: Vec<String>,
+    /// Languages the pattern is limited to, detected from the extension or shebang
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
     #[serde(default)]
     pub content_substrings: Vec<String>,
+    /// Regexes matched against the content, e.g. `\beval\s*\(`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub content_regex: Vec<String>,
+    /// Regexes for lines whose content matches are ignored, e.g. comments
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub not_content: Vec<String>,
+    /// Content matches needed for the pattern to fire (default 1)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_matches: Option<usize>,
+    /// How a match is reported: `info`, `warn` (default) or `critical`
+    #[serde(default, skip_serializing_if = "Severity::is_default")]
+    pub severity: Severity,
     pub reminder: String,
 }
 
@@ -60,7 +400,6 @@ pub struct HookInput {
     pub session_id: String,
     #[allow(dead_code)]
     pub transcript_path: Option<String>,
-    #[allow(dead_code)]
     pub cwd: Option<String>,
     pub hook_event_name: String,
     #[serde(default)]
@@ -96,6 +435,8 @@ pub struct ToolInput {
     // Bash tool parameters
     #[serde(default)]
     pub command: Option<String>,
+    #[serde(default)]
+    pub run_in_background: Option<bool>,
     #[allow(dead_code)]
     pub description: Option<String>,
 
@@ -109,6 +450,8 @@ pub struct ToolInput {
     #[serde(default)]
     pub new_string: Option<String>,   // Edit tool
     #[serde(default)]
+    pub replace_all: Option<bool>,    // Edit tool
+    #[serde(default)]
     pub edits: Option<Vec<EditOperation>>,  // MultiEdit tool
 }
 
@@ -117,6 +460,8 @@ pub struct ToolInput {
 pub struct EditOperation {
     pub old_string: String,
     pub new_string: String,
+    #[serde(default)]
+    pub replace_all: bool,
 }
 
 /// Response data sent back to Claude Code hook system.
@@ -136,10 +481,15 @@ pub struct HookOutput {
 #[serde(rename_all = "camelCase")]
 pub struct HookSpecificOutput {
     pub hook_event_name: String,
-    pub permission_decision: String,
-    pub permission_decision_reason: String,
+    /// Omitted to leave the decision to Claude Code's normal permission flow
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permission_decision: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permission_decision_reason: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_input: Option<UpdatedInput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_context: Option<String>,
 }
 
 /// Updated input data for modified tool calls
@@ -165,34 +515,70 @@ pub struct ModernHookResponse {
 
 /// Helper functions for creating modern API responses
 impl ModernHookResponse {
-    /// Create a deny response with replacement command (maintains existing behavior)
-    pub fn deny_with_replacement(decision_reason: String, replacement_command: String) -> Self {
+    /// Create a PreToolUse response from its parts
+    fn pre_tool_use(
+        permission_decision: Option<&str>,
+        decision_reason: Option<String>,
+        replacement_command: Option<String>,
+        additional_context: Option<String>,
+    ) -> Self {
         ModernHookResponse {
             hook_specific_output: Some(HookSpecificOutput {
                 hook_event_name: "PreToolUse".to_string(),
-                permission_decision: "deny".to_string(),
+                permission_decision: permission_decision.map(str::to_string),
                 permission_decision_reason: decision_reason,
-                updated_input: Some(UpdatedInput {
-                    command: Some(replacement_command),
+                updated_input: replacement_command.map(|command| UpdatedInput {
+                    command: Some(command),
                 }),
+                additional_context,
             }),
             r#continue: None,
             stop_reason: None,
         }
     }
 
+    /// Create a deny response with replacement command (maintains existing behavior)
+    pub fn deny_with_replacement(decision_reason: String, replacement_command: String) -> Self {
+        Self::pre_tool_use(Some("deny"), Some(decision_reason), Some(replacement_command), None)
+    }
+
+    /// Create a deny response without a replacement (hard block)
+    pub fn deny(decision_reason: String) -> Self {
+        Self::pre_tool_use(Some("deny"), Some(decision_reason), None, None)
+    }
+
+    /// Create an ask response so the user confirms the command
+    pub fn ask(decision_reason: String) -> Self {
+        Self::pre_tool_use(Some("ask"), Some(decision_reason), None, None)
+    }
+
+    /// Create an ask response so the user confirms the replacement command
+    pub fn ask_with_replacement(decision_reason: String, replacement_command: String) -> Self {
+        Self::pre_tool_use(Some("ask"), Some(decision_reason), Some(replacement_command), None)
+    }
+
+    /// Create an allow response that runs the replacement command instead
+    pub fn allow_with_replacement(decision_reason: String, replacement_command: String) -> Self {
+        Self::pre_tool_use(Some("allow"), Some(decision_reason), Some(replacement_command), None)
+    }
+
+    /// Create a response that attaches context for Claude without deciding
+    /// on the permission, so the normal permission flow still applies
+    pub fn with_context(additional_context: String) -> Self {
+        Self::pre_tool_use(None, None, None, Some(additional_context))
+    }
+
+    /// Attach context for Claude to a response
+    pub fn and_context(mut self, additional_context: String) -> Self {
+        if let Some(output) = &mut self.hook_specific_output {
+            output.additional_context = Some(additional_context);
+        }
+        self
+    }
+
     /// Create an allow response (for compatibility)
     pub fn allow() -> Self {
-        ModernHookResponse {
-            hook_specific_output: Some(HookSpecificOutput {
-                hook_event_name: "PreToolUse".to_string(),
-                permission_decision: "allow".to_string(),
-                permission_decision_reason: "Command allowed".to_string(),
-                updated_input: None,
-            }),
-            r#continue: None,
-            stop_reason: None,
-        }
+        Self::pre_tool_use(Some("allow"), Some("Command allowed".to_string()), None, None)
     }
     
     /// Create the correct JSON output manually for Claude Code API
@@ -203,10 +589,14 @@ impl ModernHookResponse {
             let mut hook_specific = serde_json::Map::new();
             hook_specific.insert("hookEventName".to_string(), 
                 serde_json::Value::String(hook_output.hook_event_name.clone()));
-            hook_specific.insert("permissionDecision".to_string(), 
-                serde_json::Value::String(hook_output.permission_decision.clone()));
-            hook_specific.insert("permissionDecisionReason".to_string(), 
-                serde_json::Value::String(hook_output.permission_decision_reason.clone()));
+            if let Some(decision) = &hook_output.permission_decision {
+                hook_specific.insert("permissionDecision".to_string(), 
+                    serde_json::Value::String(decision.clone()));
+            }
+            if let Some(reason) = &hook_output.permission_decision_reason {
+                hook_specific.insert("permissionDecisionReason".to_string(), 
+                    serde_json::Value::String(reason.clone()));
+            }
             
             if let Some(updated) = &hook_output.updated_input {
                 let mut updated_input = serde_json::Map::new();
@@ -217,6 +607,11 @@ impl ModernHookResponse {
                 hook_specific.insert("updatedInput".to_string(), 
                     serde_json::Value::Object(updated_input));
             }
+
+            if let Some(context) = &hook_output.additional_context {
+                hook_specific.insert("additionalContext".to_string(), 
+                    serde_json::Value::String(context.clone()));
+            }
             
             output.insert("hookSpecificOutput".to_string(), 
                 serde_json::Value::Object(hook_specific));